@@ -0,0 +1,187 @@
+use crate::{hash::HASH_B64_CFG, Graph, Hash, HashAlgo};
+use core::fmt;
+use std::collections::BTreeMap;
+
+/// minimal length of abbreviated hashes (in base64 characters),
+/// to keep them reasonably stable while the graph grows
+pub const ABBREV_MIN_LEN: usize = 7;
+
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum PrefixError {
+    #[error("invalid hash algorithm '{0}'")]
+    InvalidAlgo(String),
+
+    #[error("no event matches the prefix '{0}'")]
+    NotFound(String),
+
+    #[error("the prefix '{prefix}' is ambiguous, candidates: {candidates:?}")]
+    Ambiguous {
+        prefix: String,
+        candidates: Vec<Hash>,
+    },
+}
+
+/// An abbreviated hash, as returned by [`Graph::abbrev`].
+///
+/// The alternate format (`{:#}`) includes the hash algorithm prefix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Abbrev {
+    pub hash: Hash,
+    /// length of the base64 part
+    pub len: usize,
+}
+
+impl fmt::Display for Abbrev {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(self.hash.algo().prefix())?;
+        }
+        let encoded = base64::encode_config(self.hash.as_bytes(), HASH_B64_CFG);
+        f.write_str(&encoded[..self.len.min(encoded.len())])
+    }
+}
+
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.bytes().zip(b.bytes()).take_while(|(x, y)| x == y).count()
+}
+
+impl<Arg> Graph<Arg> {
+    /// resolve an unambiguous prefix of an event hash,
+    /// with or without the hash algorithm prefix.
+    pub fn resolve_prefix(&self, s: &str) -> Result<Hash, PrefixError> {
+        if let Ok(h) = s.parse::<Hash>() {
+            if self.events.contains_key(&h) {
+                return Ok(h);
+            }
+        }
+
+        let (algo, pfx) = match s.split_once(':') {
+            Some((algo, pfx)) => (
+                Some(
+                    algo.parse::<HashAlgo>()
+                        .map_err(|_| PrefixError::InvalidAlgo(algo.to_string()))?,
+                ),
+                pfx,
+            ),
+            None => (None, s),
+        };
+
+        let candidates: Vec<Hash> = self
+            .events
+            .keys()
+            .filter(|h| algo.map(|a| a == h.algo()).unwrap_or(true))
+            .filter(|h| base64::encode_config(h.as_bytes(), HASH_B64_CFG).starts_with(pfx))
+            .copied()
+            .collect();
+
+        match candidates[..] {
+            [] => Err(PrefixError::NotFound(s.to_string())),
+            [h] => Ok(h),
+            _ => Err(PrefixError::Ambiguous {
+                prefix: s.to_string(),
+                candidates,
+            }),
+        }
+    }
+
+    /// abbreviate a hash to the shortest prefix which is still unique in
+    /// this graph (but at least [`ABBREV_MIN_LEN`] characters long).
+    /// the prefix is unique across all hash algorithms, so it can be
+    /// resolved without the algorithm prefix.
+    pub fn abbrev(&self, h: &Hash) -> Abbrev {
+        let encoded = base64::encode_config(h.as_bytes(), HASH_B64_CFG);
+        let len = self
+            .events
+            .keys()
+            .filter(|&i| i != h)
+            .map(|i| {
+                common_prefix_len(&encoded, &base64::encode_config(i.as_bytes(), HASH_B64_CFG)) + 1
+            })
+            .max()
+            .unwrap_or(0)
+            .max(ABBREV_MIN_LEN)
+            .min(encoded.len());
+        Abbrev { hash: *h, len }
+    }
+
+    /// like [`Graph::abbrev`], but for all events at once,
+    /// which is a lot faster than abbreviating them one-by-one
+    pub fn abbrev_all(&self) -> BTreeMap<Hash, Abbrev> {
+        let mut encoded: Vec<(String, Hash)> = self
+            .events
+            .keys()
+            .map(|h| (base64::encode_config(h.as_bytes(), HASH_B64_CFG), *h))
+            .collect();
+        encoded.sort_unstable();
+        // the longest common prefix is shared with one of the neighbours
+        (0..encoded.len())
+            .map(|i| {
+                let (enc, h) = &encoded[i];
+                let len = [i.checked_sub(1), Some(i + 1)]
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|j| encoded.get(j))
+                    .map(|(other, _)| common_prefix_len(enc, other) + 1)
+                    .max()
+                    .unwrap_or(0)
+                    .max(ABBREV_MIN_LEN)
+                    .min(enc.len());
+                (*h, Abbrev { hash: *h, len })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Event;
+
+    fn mkgraph(n: usize) -> Graph<String> {
+        let mut g = Graph::default();
+        for i in 0..n {
+            g.ensure_event(Event {
+                cmd: 0,
+                arg: i.to_string(),
+                deps: Default::default(),
+            });
+        }
+        g
+    }
+
+    #[test]
+    fn abbrev_roundtrip() {
+        let g = mkgraph(300);
+        let all = g.abbrev_all();
+        assert_eq!(all.len(), 300);
+        for h in g.events.keys() {
+            let a = g.abbrev(h);
+            assert_eq!(all[h], a);
+            assert!(a.len >= ABBREV_MIN_LEN);
+            assert_eq!(g.resolve_prefix(&a.to_string()), Ok(*h));
+            assert_eq!(g.resolve_prefix(&format!("{:#}", a)), Ok(*h));
+            assert_eq!(g.resolve_prefix(&h.to_string()), Ok(*h));
+        }
+    }
+
+    #[test]
+    fn prefix_errors() {
+        let g = mkgraph(300);
+        match g.resolve_prefix("") {
+            Err(PrefixError::Ambiguous { candidates, .. }) => assert_eq!(candidates.len(), 300),
+            x => panic!("unexpected result {:?}", x),
+        }
+        assert_eq!(
+            g.resolve_prefix("sha256:"),
+            Err(PrefixError::NotFound("sha256:".to_string()))
+        );
+        assert_eq!(
+            g.resolve_prefix("md5:AAAA"),
+            Err(PrefixError::InvalidAlgo("md5".to_string()))
+        );
+        assert_eq!(
+            g.resolve_prefix("!"),
+            Err(PrefixError::NotFound("!".to_string()))
+        );
+    }
+}
@@ -0,0 +1,328 @@
+use crate::{
+    container::{arg_type_name, read_preamble, write_preamble},
+    Compression, ContainerError, Event, EventMeta, Graph, GraphError, GraphStore, Hash, Header,
+    IncludeSpec, Signatures,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Read, Write};
+
+/// the first bytes of every bundle
+pub const BUNDLE_MAGIC: [u8; 8] = *b"ESVCBNDL";
+
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+    #[error(transparent)]
+    Graph(#[from] GraphError),
+
+    #[error("missing prerequisites: {0:?}")]
+    MissingPrerequisites(BTreeSet<Hash>),
+
+    #[error("hash algorithm mismatch: bundle uses {got:?}, expected {expected:?}")]
+    HashAlgoMismatch {
+        expected: crate::HashAlgo,
+        got: crate::HashAlgo,
+    },
+
+    #[error("bundled event doesn't match its hash {0}")]
+    HashMismatch(Hash),
+
+    #[error("hash collision @ {0} detected during import")]
+    Collision(Hash),
+}
+
+/// A self-contained set of events which reproduces a named state,
+/// see [`Graph::bundle`].
+///
+/// On disk, a bundle uses the same layout as a graph container
+/// (see [`Header`]), but starts with [`BUNDLE_MAGIC`].
+// NOTE: the order of the fields is part of the data format
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Bundle<Arg> {
+    /// the name of the bundled state
+    pub name: String,
+    pub heads: BTreeSet<Hash>,
+    /// events which aren't part of the bundle, but are dependencies
+    /// of bundled events, and thus need to be present in the receiver
+    pub prerequisites: BTreeSet<Hash>,
+    /// the bundled events, in dependency order
+    pub events: Vec<(Hash, Event<Arg>)>,
+    pub meta: BTreeMap<Hash, EventMeta>,
+    pub signatures: BTreeMap<Hash, Signatures>,
+}
+
+impl<Arg: Clone> Graph<Arg> {
+    /// bundle the events which are necessary to reproduce the state `name`,
+    /// leaving out the closure of `base` (if any), which the receiver is
+    /// assumed to have already.
+    pub fn bundle(
+        &self,
+        name: &str,
+        base: Option<&BTreeSet<Hash>>,
+    ) -> Result<Bundle<Arg>, GraphError> {
+        let heads = self
+            .nstates
+            .get(name)
+            .ok_or(GraphError::DatasetNotFound)?
+            .clone();
+        let tt = match base {
+            Some(base) => self.ancestors(base)?,
+            None => BTreeSet::new(),
+        };
+        let evids = self.calculate_dependencies(
+            tt,
+            heads
+                .iter()
+                .map(|&h| (h, IncludeSpec::IncludeAll))
+                .collect(),
+        )?;
+
+        let included: BTreeSet<_> = evids.iter().copied().collect();
+        let prerequisites = evids
+            .iter()
+            .flat_map(|h| self.events[h].deps.keys())
+            .filter(|h| !included.contains(h))
+            .copied()
+            .collect();
+        Ok(Bundle {
+            name: name.to_string(),
+            heads,
+            prerequisites,
+            meta: evids
+                .iter()
+                .filter_map(|h| Some((*h, self.meta.get(h)?.clone())))
+                .collect(),
+            signatures: evids
+                .iter()
+                .filter_map(|h| Some((*h, self.signatures.get(h)?.clone())))
+                .collect(),
+            events: evids
+                .into_iter()
+                .map(|h| (h, self.events[&h].clone()))
+                .collect(),
+        })
+    }
+}
+
+impl<Arg> Graph<Arg> {
+    /// import the events of a bundle. nothing is imported if prerequisites are
+    /// missing, the bundle uses another hash algorithm than [`Graph::hash_algo`]
+    /// or any event doesn't match its hash. the bundled state isn't applied
+    /// to any local state.
+    ///
+    /// @returns the heads of the bundled state
+    pub fn import_bundle(&mut self, bundle: Bundle<Arg>) -> Result<BTreeSet<Hash>, BundleError>
+    where
+        Arg: esvc_traits::CommandArg,
+    {
+        if let Some(h) = bundle
+            .heads
+            .iter()
+            .chain(&bundle.prerequisites)
+            .chain(bundle.events.iter().map(|(h, _)| h))
+            .find(|h| h.algo() != self.hash_algo)
+        {
+            return Err(BundleError::HashAlgoMismatch {
+                expected: self.hash_algo,
+                got: h.algo(),
+            });
+        }
+
+        let missing: BTreeSet<_> = bundle
+            .prerequisites
+            .iter()
+            .filter(|h| !self.events.contains_key(h))
+            .copied()
+            .collect();
+        if !missing.is_empty() {
+            return Err(BundleError::MissingPrerequisites(missing));
+        }
+
+        // verify everything before the graph is modified
+        let mut available = bundle.prerequisites;
+        for (h, ev) in &bundle.events {
+            let serval = bincode::serialize(ev).unwrap();
+            if self.hash_algo.calculate(&serval[..]) != *h {
+                return Err(BundleError::HashMismatch(*h));
+            }
+            if let Some(dep) = ev
+                .deps
+                .keys()
+                .find(|dep| !available.contains(dep) && !self.events.contains_key(dep))
+            {
+                return Err(GraphError::DependencyNotFound(*dep).into());
+            }
+            if let Some(other) = self.events.get(h) {
+                if other != ev {
+                    return Err(BundleError::Collision(*h));
+                }
+            }
+            available.insert(*h);
+        }
+        if let Some(h) = bundle
+            .heads
+            .iter()
+            .find(|h| !available.contains(h) && !self.events.contains_key(h))
+        {
+            return Err(GraphError::DependencyNotFound(*h).into());
+        }
+
+        for (_, ev) in bundle.events {
+            self.ensure_event(ev);
+        }
+        for (h, meta) in bundle.meta {
+            // metadata of events which aren't part of the bundle is ignored
+            let _ = self.merge_meta(h, meta);
+        }
+        for (h, sigs) in bundle.signatures {
+            if self.events.contains_key(&h) {
+                for (signer, sig) in sigs {
+                    self.add_signature(h, signer, sig);
+                }
+            }
+        }
+        Ok(bundle.heads)
+    }
+}
+
+/// write a bundle, `hash_algo` should be the one of the bundled graph
+pub fn write_bundle<Arg, W>(
+    w: W,
+    engine: &str,
+    hash_algo: crate::HashAlgo,
+    compression: Compression,
+    bundle: &Bundle<Arg>,
+) -> Result<W, ContainerError>
+where
+    Arg: Serialize,
+    W: Write,
+{
+    let header = Header {
+        engine: engine.to_string(),
+        arg_type: arg_type_name::<Arg>(),
+        hash_algo,
+        compression,
+    };
+    let mut w = write_preamble(w, &BUNDLE_MAGIC, &header)?;
+    match compression {
+        Compression::None => {
+            bincode::serialize_into(&mut w, bundle)?;
+            Ok(w)
+        }
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => {
+            let mut wz = zstd::stream::write::Encoder::new(w, crate::container::ZSTD_LEVEL)?;
+            bincode::serialize_into(&mut wz, bundle)?;
+            Ok(wz.finish()?)
+        }
+        #[cfg(not(feature = "zstd"))]
+        Compression::Zstd => Err(ContainerError::UnsupportedCompression(compression)),
+    }
+}
+
+/// read a bundle, which must have been written for `engine`
+///
+/// @returns the header (which contains the hash algorithm) and the bundle
+pub fn read_bundle<Arg, R>(mut r: R, engine: &str) -> Result<(Header, Bundle<Arg>), ContainerError>
+where
+    Arg: DeserializeOwned,
+    R: Read,
+{
+    let (header, _) = read_preamble(&mut r, &BUNDLE_MAGIC)?;
+    header.check(engine)?;
+    let bundle = match header.compression {
+        Compression::None => bincode::deserialize_from(r)?,
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => bincode::deserialize_from(zstd::stream::read::Decoder::new(r)?)?,
+        #[cfg(not(feature = "zstd"))]
+        Compression::Zstd => {
+            return Err(ContainerError::UnsupportedCompression(header.compression))
+        }
+    };
+    Ok((header, bundle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HashAlgo;
+
+    fn ev(g: &mut Graph<String>, arg: &str, deps: &[Hash]) -> Hash {
+        g.ensure_event(Event {
+            cmd: 0,
+            arg: arg.to_string(),
+            deps: deps.iter().map(|&h| (h, true)).collect(),
+        })
+        .1
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut g = Graph::default();
+        let a = ev(&mut g, "a", &[]);
+        let b = ev(&mut g, "b", &[a]);
+        let c = ev(&mut g, "c", &[b]);
+        g.nstates
+            .insert("base".to_string(), [a].into_iter().collect());
+        g.nstates.insert(String::new(), [c].into_iter().collect());
+        g.merge_meta(
+            c,
+            EventMeta {
+                message: Some("c".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let bundle = g.bundle("", Some(&g.nstates["base"])).unwrap();
+        assert_eq!(bundle.prerequisites, [a].into_iter().collect());
+        assert_eq!(
+            bundle.events.iter().map(|(h, _)| *h).collect::<Vec<_>>(),
+            vec![b, c]
+        );
+        let buf =
+            write_bundle(Vec::new(), "test", g.hash_algo, Compression::None, &bundle).unwrap();
+        assert_eq!(&buf[..8], &BUNDLE_MAGIC[..]);
+        let (header, bundle2) = read_bundle::<String, _>(&buf[..], "test").unwrap();
+        assert_eq!(header.hash_algo, HashAlgo::default());
+        assert_eq!(bundle2, bundle);
+
+        // the receiver lacks the prerequisite `a`
+        let mut other = Graph::default();
+        assert!(matches!(
+            other.import_bundle(bundle.clone()),
+            Err(BundleError::MissingPrerequisites(m)) if m == [a].into_iter().collect()
+        ));
+        assert!(other.events.is_empty());
+
+        ev(&mut other, "a", &[]);
+        assert_eq!(
+            other.import_bundle(bundle.clone()).unwrap(),
+            [c].into_iter().collect()
+        );
+        assert_eq!(other.events, g.events);
+        assert_eq!(other.meta, g.meta);
+
+        // tampered events are rejected
+        let mut other = Graph::default();
+        ev(&mut other, "a", &[]);
+        let mut bad = bundle;
+        bad.events[1].1.arg = "evil".to_string();
+        assert!(matches!(
+            other.import_bundle(bad),
+            Err(BundleError::HashMismatch(h)) if h == c
+        ));
+        assert_eq!(other.events.len(), 1);
+
+        // bundles using another hash algorithm are rejected as such
+        let mut other = Graph::with_hash_algo(HashAlgo::Blake3);
+        assert!(matches!(
+            other.import_bundle(g.bundle("", None).unwrap()),
+            Err(BundleError::HashAlgoMismatch {
+                expected: HashAlgo::Blake3,
+                got: HashAlgo::Blake2b512,
+            })
+        ));
+        assert!(other.events.is_empty());
+    }
+}
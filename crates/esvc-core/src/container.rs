@@ -0,0 +1,328 @@
+use crate::{Graph, GraphError, HashAlgo};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::io::{Read, Write};
+
+/// the first bytes of every graph container
+pub const CONTAINER_MAGIC: [u8; 8] = *b"ESVCGRPH";
+
+/// the current container format version, newer versions are rejected
+///
+/// - version 1: graph
+/// - version 2: graph, reflog
+/// - version 3: graph, reflog, event metadata
+/// - version 4: graph, reflog, event metadata, signatures
+pub const CONTAINER_VERSION: u16 = 4;
+
+#[cfg(feature = "zstd")]
+pub(crate) const ZSTD_LEVEL: i32 = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Compression {
+    None,
+    Zstd,
+}
+
+/// The self-describing part of a graph container.
+///
+/// On disk, a container consists of [`CONTAINER_MAGIC`], the format version
+/// (`u16`, little endian), the bincode-serialized header, and the
+/// (possibly compressed) bincode-serialized graph, followed by the
+/// parts of it which aren't serialized with it (depending on the version).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Header {
+    /// identifies the engine which is able to interpret the events,
+    /// including the format of their arguments. it thus needs to be changed
+    /// whenever the serialized form of the arguments changes.
+    pub engine: String,
+    /// the (rust) type name of the event arguments, only for diagnostics.
+    /// it isn't checked, because it isn't stable across compiler versions
+    /// and module renames.
+    pub arg_type: String,
+    /// the hash algorithm used for new events
+    pub hash_algo: HashAlgo,
+    pub compression: Compression,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ContainerError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("(de)serialization error: {0}")]
+    Bincode(#[from] bincode::Error),
+
+    #[error(transparent)]
+    Graph(#[from] GraphError),
+
+    #[error("not an esvc graph container (invalid magic)")]
+    InvalidMagic,
+
+    #[error(
+        "unsupported container format version {0} (this build supports up to {CONTAINER_VERSION})"
+    )]
+    UnsupportedVersion(u16),
+
+    #[error("engine mismatch: file was written for '{got}', expected '{expected}'")]
+    EngineMismatch { expected: String, got: String },
+
+    #[error("corrupted record at offset {0}, which is followed by valid records")]
+    CorruptedLog(u64),
+
+    #[error("unsupported compression {0:?}")]
+    UnsupportedCompression(Compression),
+
+    #[cfg(feature = "rusqlite")]
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+}
+
+pub(crate) fn arg_type_name<Arg>() -> String {
+    core::any::type_name::<Arg>().to_string()
+}
+
+pub(crate) fn write_preamble<W: Write>(
+    mut w: W,
+    magic: &[u8; 8],
+    header: &Header,
+) -> Result<W, ContainerError> {
+    w.write_all(&magic[..])?;
+    w.write_all(&CONTAINER_VERSION.to_le_bytes())?;
+    bincode::serialize_into(&mut w, header)?;
+    Ok(w)
+}
+
+/// @returns (header, format version)
+pub(crate) fn read_preamble<R: Read>(
+    mut r: R,
+    magic: &[u8; 8],
+) -> Result<(Header, u16), ContainerError> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf[..])?;
+    if &buf != magic {
+        return Err(ContainerError::InvalidMagic);
+    }
+    let mut vbuf = [0u8; 2];
+    r.read_exact(&mut vbuf[..])?;
+    let version = u16::from_le_bytes(vbuf);
+    // versions start at 1
+    if version == 0 || version > CONTAINER_VERSION {
+        return Err(ContainerError::UnsupportedVersion(version));
+    }
+    Ok((bincode::deserialize_from(r)?, version))
+}
+
+impl Header {
+    /// check if the container matches the expectations of the reader
+    pub fn check(&self, engine: &str) -> Result<(), ContainerError> {
+        if self.engine != engine {
+            return Err(ContainerError::EngineMismatch {
+                expected: engine.to_string(),
+                got: self.engine.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// read only the header of a graph container
+pub fn read_header<R: Read>(r: R) -> Result<Header, ContainerError> {
+    read_preamble(r, &CONTAINER_MAGIC).map(|(header, _)| header)
+}
+
+fn write_body<Arg: Serialize, W: Write>(mut w: W, g: &Graph<Arg>) -> Result<(), ContainerError> {
+    bincode::serialize_into(&mut w, g)?;
+    bincode::serialize_into(&mut w, &g.reflog)?;
+    bincode::serialize_into(&mut w, &g.meta)?;
+    bincode::serialize_into(&mut w, &g.signatures)?;
+    Ok(())
+}
+
+fn read_body<Arg: DeserializeOwned, R: Read>(
+    mut r: R,
+    version: u16,
+) -> Result<Graph<Arg>, ContainerError> {
+    let mut g: Graph<Arg> = bincode::deserialize_from(&mut r)?;
+    if version >= 2 {
+        g.reflog = bincode::deserialize_from(&mut r)?;
+    }
+    if version >= 3 {
+        g.meta = bincode::deserialize_from(&mut r)?;
+    }
+    if version >= 4 {
+        g.signatures = bincode::deserialize_from(&mut r)?;
+    }
+    Ok(g)
+}
+
+/// write `g` into a graph container
+///
+/// @returns the inner writer, e.g. to allow syncing it to disk
+pub fn write_graph<Arg, W>(
+    w: W,
+    engine: &str,
+    compression: Compression,
+    g: &Graph<Arg>,
+) -> Result<W, ContainerError>
+where
+    Arg: Serialize,
+    W: Write,
+{
+    let header = Header {
+        engine: engine.to_string(),
+        arg_type: arg_type_name::<Arg>(),
+        hash_algo: g.hash_algo,
+        compression,
+    };
+    let mut w = write_preamble(w, &CONTAINER_MAGIC, &header)?;
+    match compression {
+        Compression::None => {
+            write_body(&mut w, g)?;
+            Ok(w)
+        }
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => {
+            let mut wz = zstd::stream::write::Encoder::new(w, ZSTD_LEVEL)?;
+            write_body(&mut wz, g)?;
+            Ok(wz.finish()?)
+        }
+        #[cfg(not(feature = "zstd"))]
+        Compression::Zstd => Err(ContainerError::UnsupportedCompression(compression)),
+    }
+}
+
+/// read a graph container, which must have been written for `engine`
+pub fn read_graph<Arg, R>(mut r: R, engine: &str) -> Result<Graph<Arg>, ContainerError>
+where
+    Arg: DeserializeOwned,
+    R: Read,
+{
+    let (header, version) = read_preamble(&mut r, &CONTAINER_MAGIC)?;
+    header.check(engine)?;
+    let mut g: Graph<Arg> = match header.compression {
+        Compression::None => read_body(r, version)?,
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => read_body(zstd::stream::read::Decoder::new(r)?, version)?,
+        #[cfg(not(feature = "zstd"))]
+        Compression::Zstd => {
+            return Err(ContainerError::UnsupportedCompression(header.compression))
+        }
+    };
+    g.hash_algo = header.hash_algo;
+    Ok(g)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Event, EventMeta, ReflogReason, Signature, SignerId};
+
+    fn mkgraph() -> Graph<String> {
+        let mut g = Graph::with_hash_algo(HashAlgo::Blake3);
+        let (_, a) = g.ensure_event(Event {
+            cmd: 0,
+            arg: "a".to_string(),
+            deps: Default::default(),
+        });
+        let (_, b) = g.ensure_event(Event {
+            cmd: 1,
+            arg: "b".to_string(),
+            deps: [(a, true)].into_iter().collect(),
+        });
+        g.set_state("", [b].into_iter().collect(), ReflogReason::Shelve);
+        g.merge_meta(
+            b,
+            EventMeta {
+                message: Some("b".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        g.add_signature(b, SignerId([1; 32]), Signature([2; 64]));
+        g
+    }
+
+    fn roundtrip(compression: Compression) {
+        let g = mkgraph();
+        let buf = write_graph(Vec::new(), "test", compression, &g).unwrap();
+        assert_eq!(&buf[..8], &CONTAINER_MAGIC[..]);
+        let header = read_header(&buf[..]).unwrap();
+        assert_eq!(header.compression, compression);
+        assert_eq!(header.hash_algo, HashAlgo::Blake3);
+        assert_eq!(read_graph::<String, _>(&buf[..], "test").unwrap(), g);
+    }
+
+    #[test]
+    fn roundtrip_plain() {
+        roundtrip(Compression::None);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn roundtrip_zstd() {
+        roundtrip(Compression::Zstd);
+    }
+
+    #[test]
+    fn mismatches() {
+        let buf = write_graph(Vec::new(), "test", Compression::None, &mkgraph()).unwrap();
+        assert!(matches!(
+            read_graph::<String, _>(&buf[..], "other"),
+            Err(ContainerError::EngineMismatch { .. })
+        ));
+        assert!(matches!(
+            read_graph::<String, _>(&buf[1..], "test"),
+            Err(ContainerError::InvalidMagic)
+        ));
+
+        let mut buf = buf;
+        for version in [0, CONTAINER_VERSION + 1] {
+            buf[8..10].copy_from_slice(&version.to_le_bytes());
+            assert!(matches!(
+                read_graph::<String, _>(&buf[..], "test"),
+                Err(ContainerError::UnsupportedVersion(v)) if v == version
+            ));
+        }
+    }
+
+    #[test]
+    fn arg_type_not_checked() {
+        // e.g. written by a build with another compiler version
+        let g = mkgraph();
+        let header = Header {
+            engine: "test".to_string(),
+            arg_type: "old_crate::String".to_string(),
+            hash_algo: g.hash_algo,
+            compression: Compression::None,
+        };
+        let mut buf = write_preamble(Vec::new(), &CONTAINER_MAGIC, &header).unwrap();
+        write_body(&mut buf, &g).unwrap();
+        assert_eq!(read_graph::<String, _>(&buf[..], "test").unwrap(), g);
+    }
+
+    #[test]
+    fn read_older_versions() {
+        let g = mkgraph();
+        let mut buf = write_graph(Vec::new(), "test", Compression::None, &g).unwrap();
+
+        buf[8..10].copy_from_slice(&3u16.to_le_bytes());
+        let sigs_len = bincode::serialized_size(&g.signatures).unwrap() as usize;
+        buf.truncate(buf.len() - sigs_len);
+        let g3 = read_graph::<String, _>(&buf[..], "test").unwrap();
+        assert_eq!(g3.meta, g.meta);
+        assert!(g3.signatures.is_empty());
+
+        buf[8..10].copy_from_slice(&2u16.to_le_bytes());
+        let meta_len = bincode::serialized_size(&g.meta).unwrap() as usize;
+        buf.truncate(buf.len() - meta_len);
+        let g2 = read_graph::<String, _>(&buf[..], "test").unwrap();
+        assert_eq!(g2.reflog, g.reflog);
+        assert!(g2.meta.is_empty());
+
+        buf[8..10].copy_from_slice(&1u16.to_le_bytes());
+        let reflog_len = bincode::serialized_size(&g.reflog).unwrap() as usize;
+        buf.truncate(buf.len() - reflog_len);
+        let g1 = read_graph::<String, _>(&buf[..], "test").unwrap();
+        assert_eq!(g1.events, g.events);
+        assert!(g1.reflog.is_empty());
+    }
+}
@@ -2,74 +2,139 @@
 // large parts of this were taken from `petgraph`
 // ref = https://github.com/petgraph/petgraph/blob/9ff688872b467d3e1b5adef19f5c52f519d3279c/src/dot.rs
 
-use crate::Graph;
+use crate::{
+    export::{FnFmt, Selection, STATE_COLOURS},
+    ExportOptions, Graph, GraphError,
+};
 use core::fmt::{self, Formatter, Result, Write};
 
 /// A formatter which can format a graph into the .dot format,
-/// useful for debugging and visualization
-pub struct Dot<'a, Arg>(pub &'a Graph<Arg>);
-
-fn bool2hs(x: bool) -> &'static str {
-    if x {
-        "hard"
-    } else {
-        "soft"
+/// useful for debugging and visualization.
+///
+/// If the graph is inconsistent or the selected state
+/// (see [`ExportOptions::state`]) doesn't exist, formatting falls back
+/// to the whole graph, use [`Dot::render`] to get an error instead.
+pub struct Dot<'a, Arg> {
+    pub graph: &'a Graph<Arg>,
+    pub opts: ExportOptions<'a, Arg>,
+}
+
+impl<'a, Arg> Dot<'a, Arg> {
+    pub fn new(graph: &'a Graph<Arg>) -> Self {
+        Self::with_options(graph, ExportOptions::default())
+    }
+
+    pub fn with_options(graph: &'a Graph<Arg>, opts: ExportOptions<'a, Arg>) -> Self {
+        Self { graph, opts }
     }
 }
 
 impl<Arg> Dot<'_, Arg> {
-    fn graph_fmt<AF>(&self, f: &mut Formatter<'_>, argfmtf: AF) -> Result
+    fn graph_fmt<AF>(&self, f: &mut Formatter<'_>, sel: &Selection, argfmtf: AF) -> Result
     where
         AF: Fn(&Arg, &mut Formatter<'_>) -> Result,
     {
         writeln!(f, "digraph {{")?;
 
         // labels
-        for (h, i) in &self.0.events {
+        for h in &sel.nodes {
+            let label = self.opts.label_of(&self.graph.events[h], &argfmtf);
             writeln!(
                 f,
-                "  \"{h}\" [label=\"{h}\\n{}:{}\"];",
-                i.cmd,
-                Escaped(FnFmt(&i.arg, &argfmtf)),
-                h = h,
+                "  \"{n}\" [label=\"{n}\\n{}\"];",
+                Escaped(label),
+                n = sel.names[h],
+            )?;
+        }
+        if sel.omitted != 0 {
+            writeln!(
+                f,
+                "  \"...\" [shape=plaintext, label=\"{} more events\"];",
+                sel.omitted
             )?;
         }
 
         // edges
-        for (h, i) in &self.0.events {
-            for (dep, &is_hard) in &i.deps {
+        for h in &sel.nodes {
+            for (dep, &is_hard) in &self.graph.events[h].deps {
+                if !sel.set.contains(dep) {
+                    continue;
+                }
+                let attrs = if self.opts.edge_styles {
+                    if is_hard {
+                        "style=solid"
+                    } else {
+                        "style=dashed"
+                    }
+                } else if is_hard {
+                    "label=\"hard\""
+                } else {
+                    "label=\"soft\""
+                };
                 writeln!(
                     f,
-                    "  \"{}\" -> \"{}\" [label=\"{}\"];",
-                    h,
-                    dep,
-                    bool2hs(is_hard)
+                    "  \"{}\" -> \"{}\" [{}];",
+                    sel.names[h], sel.names[dep], attrs
                 )?;
             }
         }
 
-        // clusters
-        for (nstate, deps) in &self.0.nstates {
-            writeln!(f, "  subgraph \"cluster_{}\" {{", Escaped(&nstate))?;
-            for dep in deps {
-                writeln!(f, "  \"{}\";", dep)?;
+        // states
+        for (idx, (nstate, deps)) in sel.states(&self.graph.nstates).into_iter().enumerate() {
+            if self.opts.state_colours {
+                let colour = STATE_COLOURS[idx % STATE_COLOURS.len()];
+                writeln!(
+                    f,
+                    "  \"state:{n}\" [shape=box, style=filled, fillcolor={c}, label=\"{n}\"];",
+                    n = Escaped(&nstate),
+                    c = colour,
+                )?;
+                for dep in deps {
+                    writeln!(
+                        f,
+                        "  \"state:{}\" -> \"{}\" [color={}, style=dotted];",
+                        Escaped(&nstate),
+                        sel.names[&dep],
+                        colour,
+                    )?;
+                }
+            } else {
+                writeln!(f, "  subgraph \"cluster_{}\" {{", Escaped(&nstate))?;
+                for dep in deps {
+                    writeln!(f, "  \"{}\";", sel.names[&dep])?;
+                }
+                writeln!(f, "  }}")?;
             }
-            writeln!(f, "  }}")?;
         }
 
         writeln!(f, "}}")
     }
 }
 
+impl<Arg: fmt::Display> Dot<'_, Arg> {
+    /// render the graph, unlike formatting it via `Display`,
+    /// this fails if the selection (see [`ExportOptions::state`]) fails.
+    pub fn render(&self) -> core::result::Result<String, GraphError> {
+        let sel = self.opts.select(self.graph)?;
+        // formatting into a string can't fail once the selection succeeded
+        Ok(FnFmt(&sel, |sel: &Selection, f: &mut Formatter<'_>| {
+            self.graph_fmt(f, sel, fmt::Display::fmt)
+        })
+        .to_string())
+    }
+}
+
 impl<Arg: fmt::Display> fmt::Display for Dot<'_, Arg> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        self.graph_fmt(f, fmt::Display::fmt)
+        let sel = self.opts.select_or_all(self.graph);
+        self.graph_fmt(f, &sel, fmt::Display::fmt)
     }
 }
 
 impl<Arg: fmt::Debug> fmt::Debug for Dot<'_, Arg> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        self.graph_fmt(f, fmt::Debug::fmt)
+        let sel = self.opts.select_or_all(self.graph);
+        self.graph_fmt(f, &sel, fmt::Debug::fmt)
     }
 }
 
@@ -114,14 +179,78 @@ where
     }
 }
 
-/// Format data using a specific format function
-struct FnFmt<'a, T, F>(&'a T, F);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Event, Hash};
 
-impl<'a, T, F> fmt::Display for FnFmt<'a, T, F>
-where
-    F: Fn(&'a T, &mut Formatter<'_>) -> Result,
-{
-    fn fmt(&self, f: &mut Formatter) -> Result {
-        self.1(self.0, f)
+    #[test]
+    fn options() {
+        let mut g = Graph::default();
+        let mut ev = |arg: &str, deps: &[(Hash, bool)]| {
+            g.ensure_event(Event {
+                cmd: 0,
+                arg: arg.to_string(),
+                deps: deps.iter().copied().collect(),
+            })
+            .1
+        };
+        let a = ev("a", &[]);
+        let b = ev("b", &[(a, true)]);
+        let c = ev("c", &[(b, false)]);
+        let d = ev("d", &[]);
+        g.nstates
+            .insert("main".to_string(), [c].into_iter().collect());
+        g.nstates
+            .insert("other".to_string(), [d].into_iter().collect());
+
+        let full = Dot::new(&g).to_string();
+        assert!(full.contains(&format!("\"{}\" -> \"{}\" [label=\"hard\"];", b, a)));
+        assert!(full.contains("subgraph \"cluster_other\""));
+
+        let opts = ExportOptions::default()
+            .state("main")
+            .abbrev(true)
+            .edge_styles(true)
+            .state_colours(true)
+            .node_limit(2)
+            .label(|ev: &Event<String>| ev.arg.to_uppercase());
+        let dot = Dot::with_options(&g, opts).to_string();
+        let (ab, bb, cb) = (g.abbrev(&a), g.abbrev(&b), g.abbrev(&c));
+        assert!(!dot.contains(&a.to_string()));
+        assert!(!dot.contains(&format!("\"{}\"", ab)));
+        assert!(dot.contains(&format!("\"{cb}\" [label=\"{cb}\\nC\"];")));
+        assert!(dot.contains(&format!("\"{}\" -> \"{}\" [style=dashed];", cb, bb)));
+        assert!(dot.contains("label=\"1 more events\""));
+        assert!(dot.contains("\"state:main\" [shape=box, style=filled, fillcolor=red"));
+        assert!(!dot.contains("state:other"));
+
+        assert_eq!(Dot::new(&g).render().unwrap(), full);
+        let missing = ExportOptions::default().state("nope");
+        assert!(matches!(
+            Dot::with_options(&g, missing).render(),
+            Err(GraphError::DatasetNotFound)
+        ));
+        // formatting falls back to the whole graph
+        let missing = ExportOptions::default().state("nope");
+        let sorted = |s: &str| {
+            let mut lines: Vec<_> = s.lines().map(str::to_string).collect();
+            lines.sort();
+            lines
+        };
+        let fallback = Dot::with_options(&g, missing).to_string();
+        assert_eq!(sorted(&fallback), sorted(&full));
+
+        // even if it contains a cycle
+        let (_, e) = g.ensure_event(Event {
+            cmd: 0,
+            arg: "e".to_string(),
+            deps: Default::default(),
+        });
+        g.events.get_mut(&e).unwrap().deps.insert(e, true);
+        g.rebuild_dependents();
+        assert!(Dot::new(&g).render().is_err());
+        let dot = format!("{:?}", Dot::new(&g));
+        assert!(dot.contains(&format!("\"{}\" -> \"{}\" [label=\"hard\"];", e, e)));
     }
 }
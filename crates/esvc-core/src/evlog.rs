@@ -0,0 +1,573 @@
+use crate::{
+    container::{arg_type_name, read_preamble, write_preamble},
+    Compression, ContainerError, Event, EventMeta, Graph, GraphStore, Hash, HashAlgo, Header,
+    IncludeSpec, ReflogEntry, Signature, Signatures, SignerId,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Read, Write};
+
+/// the first bytes of every event log
+pub const EVLOG_MAGIC: [u8; 8] = *b"ESVCELOG";
+
+// NOTE: the order of the variants is part of the data format
+#[derive(Deserialize)]
+pub(crate) enum LogRecord<Arg> {
+    Event(Hash, Event<Arg>),
+    State(String, BTreeSet<Hash>),
+    DropState(String),
+    /// the event got removed, e.g. by [`Graph::gc`]
+    DropEvent(Hash),
+    Reflog(ReflogEntry),
+    /// reflog entries with a lower sequence number got dropped,
+    /// see [`Graph::expire_reflog`]
+    ExpireReflog(u64),
+    /// the complete (merged) metadata of an event
+    Meta(Hash, EventMeta),
+    Signature(Hash, SignerId, Signature),
+}
+
+/// serializes exactly like `LogRecord<Arg>`
+#[derive(Serialize)]
+pub(crate) enum LogRecordRef<'a, Arg> {
+    Event(&'a Hash, &'a Event<Arg>),
+    State(&'a str, &'a BTreeSet<Hash>),
+    DropState(&'a str),
+    DropEvent(&'a Hash),
+    Reflog(&'a ReflogEntry),
+    ExpireReflog(u64),
+    Meta(&'a Hash, &'a EventMeta),
+    Signature(&'a Hash, &'a SignerId, &'a Signature),
+}
+
+/// An append-only log of graph modifications.
+///
+/// The log starts with the same preamble as a graph container (but using
+/// [`EVLOG_MAGIC`]), followed by records. Each record consists of the
+/// length (`u32`, little endian) and CRC32 checksum (`u32`, little endian)
+/// of its payload, and the bincode-serialized payload itself.
+pub struct EventLog<W> {
+    inner: W,
+    logged: BTreeSet<Hash>,
+    states: BTreeMap<String, BTreeSet<Hash>>,
+    /// sequence number of the first reflog entry which isn't logged yet
+    reflog_next: u64,
+    /// sequence number of the first reflog entry which isn't expired
+    reflog_start: u64,
+    meta: BTreeMap<Hash, EventMeta>,
+    signatures: BTreeMap<Hash, Signatures>,
+}
+
+impl<W: Write> EventLog<W> {
+    /// start a new log, writing the preamble
+    pub fn create<Arg>(w: W, engine: &str, hash_algo: HashAlgo) -> Result<Self, ContainerError> {
+        let header = Header {
+            engine: engine.to_string(),
+            arg_type: arg_type_name::<Arg>(),
+            hash_algo,
+            compression: Compression::None,
+        };
+        Ok(Self {
+            inner: write_preamble(w, &EVLOG_MAGIC, &header)?,
+            logged: BTreeSet::new(),
+            states: BTreeMap::new(),
+            reflog_next: 0,
+            reflog_start: 0,
+            meta: BTreeMap::new(),
+            signatures: BTreeMap::new(),
+        })
+    }
+
+    /// continue an existing log, which was replayed into `g`.
+    /// `w` must be positioned at the end of the valid part of the log.
+    pub fn resume<Arg>(w: W, g: &Graph<Arg>) -> Self {
+        Self {
+            inner: w,
+            logged: g.events.keys().copied().collect(),
+            states: g.nstates.clone(),
+            reflog_next: g.reflog.last().map(|e| e.seq + 1).unwrap_or(0),
+            reflog_start: g.reflog.first().map(|e| e.seq).unwrap_or(0),
+            meta: g.meta.clone(),
+            signatures: g.signatures.clone(),
+        }
+    }
+
+    fn append<Arg: Serialize>(
+        &mut self,
+        rec: &LogRecordRef<'_, Arg>,
+    ) -> Result<(), ContainerError> {
+        write_record(&mut self.inner, rec)?;
+        Ok(())
+    }
+
+    pub fn append_event<Arg: Serialize>(
+        &mut self,
+        h: Hash,
+        ev: &Event<Arg>,
+    ) -> Result<(), ContainerError> {
+        if !self.logged.contains(&h) {
+            self.append(&LogRecordRef::Event(&h, ev))?;
+            self.logged.insert(h);
+        }
+        Ok(())
+    }
+
+    pub fn append_state(&mut self, name: &str, st: &BTreeSet<Hash>) -> Result<(), ContainerError> {
+        if self.states.get(name) != Some(st) {
+            self.append(&LogRecordRef::<()>::State(name, st))?;
+            self.states.insert(name.to_string(), st.clone());
+        }
+        Ok(())
+    }
+
+    pub fn drop_state(&mut self, name: &str) -> Result<(), ContainerError> {
+        if self.states.remove(name).is_some() {
+            self.append(&LogRecordRef::<()>::DropState(name))?;
+        }
+        Ok(())
+    }
+
+    /// log the expiry of all reflog entries before `seq`
+    pub fn expire_reflog(&mut self, seq: u64) -> Result<(), ContainerError> {
+        if seq > self.reflog_start {
+            self.append(&LogRecordRef::<()>::ExpireReflog(seq))?;
+            self.reflog_start = seq;
+        }
+        Ok(())
+    }
+
+    /// log the removal of an event, including its metadata and signatures
+    pub fn drop_event(&mut self, h: &Hash) -> Result<(), ContainerError> {
+        if self.logged.remove(h) {
+            self.append(&LogRecordRef::<()>::DropEvent(h))?;
+            self.meta.remove(h);
+            self.signatures.remove(h);
+        }
+        Ok(())
+    }
+
+    pub fn append_reflog(&mut self, entry: &ReflogEntry) -> Result<(), ContainerError> {
+        if entry.seq >= self.reflog_next {
+            self.append(&LogRecordRef::<()>::Reflog(entry))?;
+            self.reflog_next = entry.seq + 1;
+        }
+        Ok(())
+    }
+
+    pub fn append_meta(&mut self, evid: &Hash, meta: &EventMeta) -> Result<(), ContainerError> {
+        if self.meta.get(evid) != Some(meta) {
+            self.append(&LogRecordRef::<()>::Meta(evid, meta))?;
+            self.meta.insert(*evid, meta.clone());
+        }
+        Ok(())
+    }
+
+    pub fn append_signature(
+        &mut self,
+        subject: &Hash,
+        signer: &SignerId,
+        sig: &Signature,
+    ) -> Result<(), ContainerError> {
+        let known = self
+            .signatures
+            .get(subject)
+            .and_then(|sigs| sigs.get(signer));
+        if known != Some(sig) {
+            self.append(&LogRecordRef::<()>::Signature(subject, signer, sig))?;
+            self.signatures
+                .entry(*subject)
+                .or_default()
+                .insert(*signer, *sig);
+        }
+        Ok(())
+    }
+
+    /// like [`Graph::ensure_event`], but also appends the event
+    /// to the log if it got inserted
+    pub fn ensure_event<Arg>(
+        &mut self,
+        g: &mut Graph<Arg>,
+        ev: Event<Arg>,
+    ) -> Result<(Option<Event<Arg>>, Hash), ContainerError>
+    where
+        Arg: esvc_traits::CommandArg,
+    {
+        let (collinfo, h) = g.ensure_event(ev);
+        if collinfo.is_none() {
+            self.append_event(h, &g.events[&h])?;
+        }
+        Ok((collinfo, h))
+    }
+
+    /// append everything which changed in `g` since the last sync,
+    /// e.g. events which were inserted by `WorkCache::shelve_event`.
+    /// new events are appended in dependency order, so that a truncated
+    /// log never contains events without their dependencies.
+    /// removed events are logged last, after the states which
+    /// referenced them.
+    pub fn sync<Arg: Serialize>(&mut self, g: &Graph<Arg>) -> Result<(), ContainerError> {
+        let new_events = g.calculate_dependencies(
+            self.logged.clone(),
+            g.events
+                .keys()
+                .filter(|h| !self.logged.contains(h))
+                .map(|&h| (h, IncludeSpec::IncludeAll))
+                .collect(),
+        )?;
+        for h in new_events {
+            self.append_event(h, &g.events[&h])?;
+        }
+
+        let dropped: Vec<_> = self
+            .states
+            .keys()
+            .filter(|name| !g.nstates.contains_key(*name))
+            .cloned()
+            .collect();
+        for name in dropped {
+            self.drop_state(&name)?;
+        }
+        for (name, st) in &g.nstates {
+            self.append_state(name, st)?;
+        }
+        for entry in &g.reflog {
+            self.append_reflog(entry)?;
+        }
+        if let Some(first) = g.reflog.first() {
+            self.expire_reflog(first.seq)?;
+        }
+        for (evid, meta) in &g.meta {
+            self.append_meta(evid, meta)?;
+        }
+        for (subject, sigs) in &g.signatures {
+            for (signer, sig) in sigs {
+                self.append_signature(subject, signer, sig)?;
+            }
+        }
+
+        let removed: Vec<_> = self
+            .logged
+            .iter()
+            .filter(|h| !g.events.contains_key(h))
+            .copied()
+            .collect();
+        for h in removed {
+            self.drop_event(&h)?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// @returns the number of bytes written
+pub(crate) fn write_record<W: Write, Arg: Serialize>(
+    mut w: W,
+    rec: &LogRecordRef<'_, Arg>,
+) -> Result<u64, ContainerError> {
+    let payload = bincode::serialize(rec)?;
+    let len = u32::try_from(payload.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "log record too large"))?;
+    let mut buf = Vec::with_capacity(8 + payload.len());
+    buf.extend_from_slice(&len.to_le_bytes());
+    buf.extend_from_slice(&crc32fast::hash(&payload[..]).to_le_bytes());
+    buf.extend_from_slice(&payload[..]);
+    w.write_all(&buf[..])?;
+    Ok(buf.len() as u64)
+}
+
+/// @returns the payload of the next record,
+/// or `None` if it is truncated or corrupted
+pub(crate) fn read_record<R: Read>(mut r: R) -> io::Result<Option<Vec<u8>>> {
+    let mut rhdr = [0u8; 8];
+    if read_full(&mut r, &mut rhdr[..])? < rhdr.len() {
+        return Ok(None);
+    }
+    let len = u32::from_le_bytes(rhdr[..4].try_into().unwrap());
+    let checksum = u32::from_le_bytes(rhdr[4..].try_into().unwrap());
+    let mut payload = Vec::new();
+    r.take(len.into()).read_to_end(&mut payload)?;
+    if payload.len() < len as usize || crc32fast::hash(&payload[..]) != checksum {
+        return Ok(None);
+    }
+    Ok(Some(payload))
+}
+
+/// the result of [`replay_log`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Replay<Arg> {
+    pub graph: Graph<Arg>,
+    /// the length of the valid part of the log (in bytes),
+    /// the log should be truncated to this length before appending to it.
+    pub valid_len: u64,
+    /// the number of bytes of the (truncated or corrupted) tail
+    /// which got dropped
+    pub dropped: u64,
+}
+
+struct CountingReader<R> {
+    inner: R,
+    pos: u64,
+    /// the bytes read since the start of the current record
+    record: Vec<u8>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.pos += n as u64;
+        self.record.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// @returns the offset of the first valid record in `buf`, if any
+fn find_record(buf: &[u8]) -> Option<usize> {
+    (0..buf.len().saturating_sub(8)).find(|&i| {
+        let len = u32::from_le_bytes(buf[i..i + 4].try_into().unwrap()) as usize;
+        let checksum = u32::from_le_bytes(buf[i + 4..i + 8].try_into().unwrap());
+        let payload = &buf[i + 8..];
+        // payloads are never empty, which also skips zeroed areas
+        len != 0 && len <= payload.len() && crc32fast::hash(&payload[..len]) == checksum
+    })
+}
+
+/// read as many bytes as possible, until `buf` is full or EOF is reached
+fn read_full<R: Read>(mut r: R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut n = 0;
+    while n < buf.len() {
+        match r.read(&mut buf[n..]) {
+            Ok(0) => break,
+            Ok(m) => n += m,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(n)
+}
+
+pub(crate) struct RecordReader<R> {
+    inner: CountingReader<R>,
+    pub(crate) header: Header,
+    /// offset of the end of the last valid record
+    pub(crate) valid_len: u64,
+}
+
+impl<R: Read> RecordReader<R> {
+    pub(crate) fn new(r: R, engine: &str) -> Result<Self, ContainerError> {
+        let mut inner = CountingReader {
+            inner: r,
+            pos: 0,
+            record: Vec::new(),
+        };
+        let (header, _) = read_preamble(&mut inner, &EVLOG_MAGIC)?;
+        header.check(engine)?;
+        Ok(Self {
+            valid_len: inner.pos,
+            inner,
+            header,
+        })
+    }
+
+    /// @returns the offset and payload of the next valid record,
+    /// or `None` at the end of the valid part of the log.
+    pub(crate) fn next_record(&mut self) -> io::Result<Option<(u64, Vec<u8>)>> {
+        let offset = self.inner.pos;
+        self.inner.record.clear();
+        let ret = read_record(&mut self.inner)?;
+        if ret.is_some() {
+            self.valid_len = self.inner.pos;
+        }
+        Ok(ret.map(|payload| (offset, payload)))
+    }
+
+    /// consume the remaining (invalid) part of the log, which is only
+    /// accepted if it doesn't contain any valid records, e.g. because
+    /// of a crash while appending.
+    /// @returns the length of the invalid part in bytes
+    pub(crate) fn finish(mut self) -> Result<u64, ContainerError> {
+        // contains the invalid record, if any
+        let mut tail = core::mem::take(&mut self.inner.record);
+        self.inner.inner.read_to_end(&mut tail)?;
+        match tail.get(1..).and_then(find_record) {
+            Some(_) => Err(ContainerError::CorruptedLog(self.valid_len)),
+            None => Ok(tail.len() as u64),
+        }
+    }
+}
+
+/// replay a log written for `engine` into a graph.
+/// a truncated or corrupted tail (e.g. because of a crash while appending)
+/// gets dropped. a corrupted record which is followed by valid records
+/// results in [`ContainerError::CorruptedLog`] instead, because
+/// dropping it would lose these.
+pub fn replay_log<Arg, R>(r: R, engine: &str) -> Result<Replay<Arg>, ContainerError>
+where
+    Arg: DeserializeOwned,
+    R: Read,
+{
+    let mut rr = RecordReader::new(r, engine)?;
+    let mut graph = Graph::with_hash_algo(rr.header.hash_algo);
+    let mut removed_any = false;
+    while let Some((_, payload)) = rr.next_record()? {
+        match bincode::deserialize(&payload[..])? {
+            LogRecord::Event(h, ev) => {
+                graph.insert_indexed(h, ev);
+            }
+            LogRecord::State(name, st) => {
+                graph.nstates.insert(name, st);
+            }
+            LogRecord::DropState(name) => {
+                graph.nstates.remove(&name);
+            }
+            LogRecord::Reflog(entry) => {
+                graph.reflog.push(entry);
+            }
+            LogRecord::Meta(evid, meta) => {
+                graph.meta.insert(evid, meta);
+            }
+            LogRecord::Signature(subject, signer, sig) => {
+                graph.add_signature(subject, signer, sig);
+            }
+            LogRecord::DropEvent(h) => {
+                graph.events.remove(&h);
+                graph.meta.remove(&h);
+                graph.signatures.remove(&h);
+                removed_any = true;
+            }
+            LogRecord::ExpireReflog(seq) => {
+                graph.reflog.retain(|e| e.seq >= seq);
+            }
+        }
+    }
+    if removed_any {
+        graph.rebuild_dependents();
+    }
+    let valid_len = rr.valid_len;
+    let dropped = rr.finish()?;
+    Ok(Replay {
+        graph,
+        valid_len,
+        dropped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReflogReason;
+
+    fn ev(arg: &str, deps: &[Hash]) -> Event<String> {
+        Event {
+            cmd: 0,
+            arg: arg.to_string(),
+            deps: deps.iter().map(|&h| (h, true)).collect(),
+        }
+    }
+
+    fn mklog() -> (Graph<String>, Vec<u8>) {
+        let mut g = Graph::default();
+        let mut log = EventLog::create::<String>(Vec::new(), "test", g.hash_algo).unwrap();
+        let (_, a) = log.ensure_event(&mut g, ev("a", &[])).unwrap();
+        let (_, b) = g.ensure_event(ev("b", &[a]));
+        g.set_state("x", [a].into_iter().collect(), ReflogReason::Manual);
+        g.set_state("", [b].into_iter().collect(), ReflogReason::Shelve);
+        log.sync(&g).unwrap();
+        g.remove_state("x", ReflogReason::Manual);
+        log.sync(&g).unwrap();
+        // nothing changed, nothing should be appended
+        let len = log.get_ref().len();
+        log.sync(&g).unwrap();
+        assert_eq!(log.get_ref().len(), len);
+        (g, log.into_inner())
+    }
+
+    #[test]
+    fn replay() {
+        let (g, buf) = mklog();
+        let r = replay_log::<String, _>(&buf[..], "test").unwrap();
+        assert_eq!(r.graph, g);
+        assert_eq!(r.valid_len, buf.len() as u64);
+        assert_eq!(r.dropped, 0);
+    }
+
+    #[test]
+    fn replay_truncated() {
+        let (g, buf) = mklog();
+        // cut into the last record (Reflog), after the DropState record
+        let r = replay_log::<String, _>(&buf[..buf.len() - 3], "test").unwrap();
+        assert_eq!(r.graph.events, g.events);
+        assert!(!r.graph.nstates.contains_key("x"));
+        assert_eq!(r.graph.reflog.len(), g.reflog.len() - 1);
+        assert_eq!(r.dropped, (buf.len() - 3) as u64 - r.valid_len);
+        assert!(r.dropped > 0);
+
+        // resume appending after truncation
+        let mut buf2 = buf[..r.valid_len as usize].to_vec();
+        let mut log = EventLog::resume(&mut buf2, &r.graph);
+        log.sync(&g).unwrap();
+        let r2 = replay_log::<String, _>(&buf2[..], "test").unwrap();
+        assert_eq!(r2.graph, g);
+        assert_eq!(r2.dropped, 0);
+    }
+
+    #[test]
+    fn replay_gc() {
+        let (mut g, buf) = mklog();
+        let (_, c) = g.ensure_event(ev("c", &[]));
+        g.merge_meta(
+            c,
+            EventMeta {
+                message: Some("c".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let r = replay_log::<String, _>(&buf[..], "test").unwrap();
+        let mut buf = buf;
+        let mut log = EventLog::resume(&mut buf, &r.graph);
+        log.sync(&g).unwrap();
+
+        assert_eq!(g.gc(false).unwrap(), [c].into_iter().collect());
+        g.expire_reflog(1);
+        log.sync(&g).unwrap();
+        let r = replay_log::<String, _>(&buf[..], "test").unwrap();
+        assert!(!r.graph.events.contains_key(&c));
+        assert!(!r.graph.meta.contains_key(&c));
+        assert_eq!(r.graph, g);
+    }
+
+    #[test]
+    fn replay_corrupted() {
+        let (g, mut buf) = mklog();
+        let last = buf.len() - 1;
+        buf[last] ^= 0xff;
+        let r = replay_log::<String, _>(&buf[..], "test").unwrap();
+        assert_eq!(r.graph.events, g.events);
+        assert!(r.dropped > 0);
+        assert_eq!(r.valid_len + r.dropped, buf.len() as u64);
+    }
+
+    #[test]
+    fn replay_corrupted_middle() {
+        let (_, mut buf) = mklog();
+        let mut rr = RecordReader::new(&buf[..], "test").unwrap();
+        let (offset, _) = rr.next_record().unwrap().unwrap();
+        // corrupt the payload of the first record, the later ones must not be dropped
+        buf[offset as usize + 8] ^= 0xff;
+        assert!(matches!(
+            replay_log::<String, _>(&buf[..], "test"),
+            Err(ContainerError::CorruptedLog(o)) if o == offset
+        ));
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{Event, Graph, GraphError, GraphStore, Hash, IncludeSpec};
+use core::fmt;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// colours used to distinguish named states
+pub(crate) const STATE_COLOURS: [&str; 8] = [
+    "red",
+    "blue",
+    "darkgreen",
+    "orange",
+    "purple",
+    "brown",
+    "magenta",
+    "cyan4",
+];
+
+/// a custom label of an event, see [`ExportOptions::label`]
+pub type LabelFn<'a, Arg> = Box<dyn Fn(&Event<Arg>) -> String + 'a>;
+
+/// Options for graph exporters like [`Dot`](crate::Dot).
+///
+/// The defaults export the whole graph with full hashes.
+pub struct ExportOptions<'a, Arg> {
+    pub state: Option<String>,
+    pub abbrev: bool,
+    pub edge_styles: bool,
+    pub state_colours: bool,
+    pub node_limit: Option<usize>,
+    pub label: Option<LabelFn<'a, Arg>>,
+}
+
+impl<Arg> Default for ExportOptions<'_, Arg> {
+    fn default() -> Self {
+        Self {
+            state: None,
+            abbrev: false,
+            edge_styles: false,
+            state_colours: false,
+            node_limit: None,
+            label: None,
+        }
+    }
+}
+
+impl<'a, Arg> ExportOptions<'a, Arg> {
+    /// only export the closure of the named state `name`
+    pub fn state(mut self, name: impl Into<String>) -> Self {
+        self.state = Some(name.into());
+        self
+    }
+
+    /// abbreviate hashes (see [`Graph::abbrev`])
+    pub fn abbrev(mut self, x: bool) -> Self {
+        self.abbrev = x;
+        self
+    }
+
+    /// distinguish hard and soft dependencies by the edge style
+    /// (solid vs. dashed) instead of edge labels
+    pub fn edge_styles(mut self, x: bool) -> Self {
+        self.edge_styles = x;
+        self
+    }
+
+    /// give each named state its own colour
+    pub fn state_colours(mut self, x: bool) -> Self {
+        self.state_colours = x;
+        self
+    }
+
+    /// export at most `n` events. dependencies are left out before
+    /// the events which depend on them, so the newest events are kept.
+    pub fn node_limit(mut self, n: usize) -> Self {
+        self.node_limit = Some(n);
+        self
+    }
+
+    /// use a custom label for events instead of `cmd:arg`
+    pub fn label(mut self, f: impl Fn(&Event<Arg>) -> String + 'a) -> Self {
+        self.label = Some(Box::new(f));
+        self
+    }
+
+    /// the (unescaped) label of `ev`, `argfmtf` is used to format
+    /// the argument if no custom label is set
+    pub(crate) fn label_of<AF>(&self, ev: &Event<Arg>, argfmtf: AF) -> String
+    where
+        AF: Fn(&Arg, &mut fmt::Formatter<'_>) -> fmt::Result,
+    {
+        match &self.label {
+            Some(labelf) => labelf(ev),
+            None => format!("{}:{}", ev.cmd, FnFmt(&ev.arg, argfmtf)),
+        }
+    }
+
+    pub(crate) fn select(&self, g: &Graph<Arg>) -> Result<Selection, GraphError> {
+        let roots: BTreeMap<_, _> = match &self.state {
+            Some(name) => g
+                .nstates
+                .get(name)
+                .ok_or(GraphError::DatasetNotFound)?
+                .iter()
+                .map(|&h| (h, IncludeSpec::IncludeAll))
+                .collect(),
+            None => g
+                .events
+                .keys()
+                .map(|&h| (h, IncludeSpec::IncludeAll))
+                .collect(),
+        };
+        let mut nodes = g.calculate_dependencies(Default::default(), roots)?;
+        nodes.reverse();
+        let omitted = match self.node_limit {
+            Some(n) if n < nodes.len() => {
+                let omitted = nodes.len() - n;
+                nodes.truncate(n);
+                omitted
+            }
+            _ => 0,
+        };
+
+        Ok(self.selection(g, nodes, omitted))
+    }
+
+    /// like [`ExportOptions::select`], but falls back to all events
+    /// (in no particular order) if the selection fails, because
+    /// formatting via `Display` can't report the reason.
+    pub(crate) fn select_or_all(&self, g: &Graph<Arg>) -> Selection {
+        self.select(g)
+            .unwrap_or_else(|_| self.selection(g, g.events.keys().copied().collect(), 0))
+    }
+
+    fn selection(&self, g: &Graph<Arg>, nodes: Vec<Hash>, omitted: usize) -> Selection {
+        let names = if self.abbrev {
+            let mut all = g.abbrev_all();
+            nodes
+                .iter()
+                .map(|h| (*h, all.remove(h).unwrap().to_string()))
+                .collect()
+        } else {
+            nodes.iter().map(|h| (*h, h.to_string())).collect()
+        };
+        Selection {
+            set: nodes.iter().copied().collect(),
+            nodes,
+            omitted,
+            names,
+        }
+    }
+}
+
+/// The events which get exported.
+pub(crate) struct Selection {
+    /// dependents come before their dependencies
+    pub nodes: Vec<Hash>,
+    pub set: BTreeSet<Hash>,
+    /// the number of events left out because of the node limit
+    pub omitted: usize,
+    /// the (possibly abbreviated) names of the events
+    pub names: BTreeMap<Hash, String>,
+}
+
+impl Selection {
+    /// the named states with the selected events which are part of them
+    pub fn states<'g>(
+        &self,
+        nstates: &'g BTreeMap<String, BTreeSet<Hash>>,
+    ) -> Vec<(&'g str, Vec<Hash>)> {
+        nstates
+            .iter()
+            .filter_map(|(name, members)| {
+                let members: Vec<_> = members
+                    .iter()
+                    .filter(|h| self.set.contains(h))
+                    .copied()
+                    .collect();
+                (!members.is_empty()).then_some((&name[..], members))
+            })
+            .collect()
+    }
+}
+
+/// Format data using a specific format function
+pub(crate) struct FnFmt<'a, T, F>(pub &'a T, pub F);
+
+impl<'a, T, F> fmt::Display for FnFmt<'a, T, F>
+where
+    F: Fn(&'a T, &mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.1(self.0, f)
+    }
+}
@@ -0,0 +1,483 @@
+use crate::{
+    container::{arg_type_name, write_preamble},
+    evlog::{read_record, write_record, LogRecord, LogRecordRef, RecordReader},
+    Compression, ContainerError, Event, EventMeta, GraphError, GraphStore, Hash, HashAlgo, Header,
+    Signature, Signatures, SignerId, EVLOG_MAGIC,
+};
+use core::fmt;
+use serde::{de::DeserializeOwned, Serialize};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::rc::Rc;
+
+/// default number of events a [`FileStore`] keeps in memory
+pub const FILESTORE_CACHE_CAPACITY: usize = 1024;
+
+struct IndexEntry {
+    /// offset of the record containing the event
+    offset: u64,
+    deps: BTreeMap<Hash, bool>,
+}
+
+/// a simple least-recently-used cache
+struct Lru<T> {
+    capacity: usize,
+    tick: u64,
+    entries: BTreeMap<Hash, (u64, T)>,
+    order: BTreeMap<u64, Hash>,
+}
+
+impl<T: Clone> Lru<T> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            entries: BTreeMap::new(),
+            order: BTreeMap::new(),
+        }
+    }
+
+    fn get(&mut self, h: &Hash) -> Option<T> {
+        let (tick, x) = self.entries.get_mut(h)?;
+        self.order.remove(tick);
+        self.tick += 1;
+        *tick = self.tick;
+        self.order.insert(self.tick, *h);
+        Some(x.clone())
+    }
+
+    fn insert(&mut self, h: Hash, x: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        if let Some((old_tick, _)) = self.entries.insert(h, (self.tick, x)) {
+            self.order.remove(&old_tick);
+        }
+        self.order.insert(self.tick, h);
+        while self.entries.len() > self.capacity {
+            let (_, oldest) = self.order.pop_first().unwrap();
+            self.entries.remove(&oldest);
+        }
+    }
+}
+
+/// A file which can be truncated, see [`FileStore::open`].
+pub trait Truncate {
+    fn truncate(&mut self, len: u64) -> io::Result<()>;
+}
+
+impl Truncate for std::fs::File {
+    fn truncate(&mut self, len: u64) -> io::Result<()> {
+        self.set_len(len)
+    }
+}
+
+impl Truncate for io::Cursor<Vec<u8>> {
+    fn truncate(&mut self, len: u64) -> io::Result<()> {
+        self.get_mut().truncate(len as usize);
+        Ok(())
+    }
+}
+
+impl<T: Truncate + ?Sized> Truncate for &mut T {
+    fn truncate(&mut self, len: u64) -> io::Result<()> {
+        (**self).truncate(len)
+    }
+}
+
+fn storage_err(e: impl fmt::Display) -> GraphError {
+    GraphError::Storage(e.to_string())
+}
+
+/// A [`GraphStore`] which only keeps an index of the dependencies and the
+/// named states in memory, the events themselves are read on demand
+/// (with a small cache in front of the file).
+///
+/// The file uses the event log format (see [`EventLog`](crate::EventLog)),
+/// so it can also be loaded using [`replay_log`](crate::replay_log).
+pub struct FileStore<Arg, F> {
+    file: RefCell<F>,
+    hash_algo: HashAlgo,
+    index: BTreeMap<Hash, IndexEntry>,
+    nstates: BTreeMap<String, BTreeSet<Hash>>,
+    meta: BTreeMap<Hash, EventMeta>,
+    signatures: BTreeMap<Hash, Signatures>,
+    cache: RefCell<Lru<Rc<Event<Arg>>>>,
+    /// end of the valid part of the file, new records get written here
+    end: u64,
+    dropped: u64,
+}
+
+impl<Arg, F: Read + Write + Seek> FileStore<Arg, F> {
+    /// initialize a new store, `f` should be empty
+    pub fn create(mut f: F, engine: &str, hash_algo: HashAlgo) -> Result<Self, ContainerError> {
+        f.seek(SeekFrom::Start(0))?;
+        let header = Header {
+            engine: engine.to_string(),
+            arg_type: arg_type_name::<Arg>(),
+            hash_algo,
+            compression: Compression::None,
+        };
+        let mut f = write_preamble(f, &EVLOG_MAGIC, &header)?;
+        let end = f.stream_position()?;
+        Ok(Self {
+            file: RefCell::new(f),
+            hash_algo,
+            index: BTreeMap::new(),
+            nstates: BTreeMap::new(),
+            meta: BTreeMap::new(),
+            signatures: BTreeMap::new(),
+            cache: RefCell::new(Lru::new(FILESTORE_CACHE_CAPACITY)),
+            end,
+            dropped: 0,
+        })
+    }
+
+    /// open an existing store, which must have been written for `engine`.
+    /// a truncated or corrupted tail gets dropped (see [`FileStore::dropped`]),
+    /// and the file is truncated accordingly. corrupted records which
+    /// are followed by valid ones are rejected, like in [`replay_log`](crate::replay_log).
+    pub fn open(mut f: F, engine: &str) -> Result<Self, ContainerError>
+    where
+        Arg: DeserializeOwned,
+        F: Truncate,
+    {
+        f.seek(SeekFrom::Start(0))?;
+        let mut index = BTreeMap::new();
+        let mut nstates = BTreeMap::new();
+        let mut meta = BTreeMap::new();
+        let mut signatures = BTreeMap::<_, Signatures>::new();
+        let mut rr = RecordReader::new(io::BufReader::new(&mut f), engine)?;
+        while let Some((offset, payload)) = rr.next_record()? {
+            match bincode::deserialize::<LogRecord<Arg>>(&payload[..])? {
+                LogRecord::Event(h, ev) => {
+                    index.insert(
+                        h,
+                        IndexEntry {
+                            offset,
+                            deps: ev.deps,
+                        },
+                    );
+                }
+                LogRecord::State(name, st) => {
+                    nstates.insert(name, st);
+                }
+                LogRecord::DropState(name) => {
+                    nstates.remove(&name);
+                }
+                // the reflog isn't kept by this store
+                LogRecord::Reflog(_) | LogRecord::ExpireReflog(_) => {}
+                LogRecord::Meta(evid, m) => {
+                    meta.insert(evid, m);
+                }
+                LogRecord::Signature(subject, signer, sig) => {
+                    signatures.entry(subject).or_default().insert(signer, sig);
+                }
+                LogRecord::DropEvent(h) => {
+                    index.remove(&h);
+                    meta.remove(&h);
+                    signatures.remove(&h);
+                }
+            }
+        }
+        let hash_algo = rr.header.hash_algo;
+        let end = rr.valid_len;
+        let dropped = rr.finish()?;
+        if dropped != 0 {
+            f.truncate(end)?;
+        }
+        Ok(Self {
+            file: RefCell::new(f),
+            hash_algo,
+            index,
+            nstates,
+            meta,
+            signatures,
+            cache: RefCell::new(Lru::new(FILESTORE_CACHE_CAPACITY)),
+            end,
+            dropped,
+        })
+    }
+
+    /// set the maximum number of events kept in memory, this clears the cache
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache = RefCell::new(Lru::new(capacity));
+        self
+    }
+
+    /// the length of the valid part of the file (in bytes)
+    pub fn valid_len(&self) -> u64 {
+        self.end
+    }
+
+    /// the number of bytes of the (truncated or corrupted) tail
+    /// which got ignored while opening the store
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.file.get_mut().flush()
+    }
+
+    pub fn into_inner(self) -> F {
+        self.file.into_inner()
+    }
+
+    fn append(&mut self, rec: &LogRecordRef<'_, Arg>) -> Result<(), GraphError>
+    where
+        Arg: Serialize,
+    {
+        let f = self.file.get_mut();
+        f.seek(SeekFrom::Start(self.end)).map_err(storage_err)?;
+        self.end += write_record(f, rec).map_err(storage_err)?;
+        Ok(())
+    }
+}
+
+impl<Arg, F> GraphStore<Arg> for FileStore<Arg, F>
+where
+    Arg: Serialize + DeserializeOwned,
+    F: Read + Write + Seek,
+{
+    type EventRef<'a>
+        = Rc<Event<Arg>>
+    where
+        Self: 'a;
+
+    fn hash_algo(&self) -> HashAlgo {
+        self.hash_algo
+    }
+
+    fn get_event(&self, evid: &Hash) -> Result<Option<Rc<Event<Arg>>>, GraphError> {
+        let offset = match self.index.get(evid) {
+            Some(ie) => ie.offset,
+            None => return Ok(None),
+        };
+        if let Some(ev) = self.cache.borrow_mut().get(evid) {
+            return Ok(Some(ev));
+        }
+
+        let corrupted =
+            || GraphError::Storage(format!("corrupted record of {} @ offset {}", evid, offset));
+        let payload = {
+            let mut f = self.file.borrow_mut();
+            f.seek(SeekFrom::Start(offset)).map_err(storage_err)?;
+            read_record(&mut *f)
+                .map_err(storage_err)?
+                .ok_or_else(corrupted)?
+        };
+        let ev = match bincode::deserialize(&payload[..]).map_err(storage_err)? {
+            LogRecord::Event(h, ev) if h == *evid => Rc::new(ev),
+            _ => return Err(corrupted()),
+        };
+        self.cache.borrow_mut().insert(*evid, Rc::clone(&ev));
+        Ok(Some(ev))
+    }
+
+    fn get_deps(&self, evid: &Hash) -> Result<Option<Cow<'_, BTreeMap<Hash, bool>>>, GraphError> {
+        Ok(self.index.get(evid).map(|ie| Cow::Borrowed(&ie.deps)))
+    }
+
+    fn insert_event(&mut self, evid: Hash, ev: Event<Arg>) -> Result<(), GraphError> {
+        let offset = self.end;
+        self.append(&LogRecordRef::Event(&evid, &ev))?;
+        self.index.insert(
+            evid,
+            IndexEntry {
+                offset,
+                deps: ev.deps.clone(),
+            },
+        );
+        self.cache.get_mut().insert(evid, Rc::new(ev));
+        Ok(())
+    }
+
+    fn nstate_names(&self) -> Result<Vec<String>, GraphError> {
+        Ok(self.nstates.keys().cloned().collect())
+    }
+
+    fn get_nstate(&self, name: &str) -> Result<Option<BTreeSet<Hash>>, GraphError> {
+        Ok(self.nstates.get(name).cloned())
+    }
+
+    fn set_nstate(&mut self, name: &str, st: BTreeSet<Hash>) -> Result<(), GraphError> {
+        if self.nstates.get(name) != Some(&st) {
+            self.append(&LogRecordRef::State(name, &st))?;
+            self.nstates.insert(name.to_string(), st);
+        }
+        Ok(())
+    }
+
+    fn remove_nstate(&mut self, name: &str) -> Result<Option<BTreeSet<Hash>>, GraphError> {
+        if !self.nstates.contains_key(name) {
+            return Ok(None);
+        }
+        self.append(&LogRecordRef::DropState(name))?;
+        Ok(self.nstates.remove(name))
+    }
+
+    fn get_meta(&self, evid: &Hash) -> Result<Option<EventMeta>, GraphError> {
+        Ok(self.meta.get(evid).cloned())
+    }
+
+    fn merge_meta(&mut self, evid: Hash, meta: EventMeta) -> Result<(), GraphError> {
+        if !self.index.contains_key(&evid) {
+            return Err(GraphError::DependencyNotFound(evid));
+        }
+        let merged = match self.meta.get(&evid) {
+            Some(old) => old.clone().merge(meta),
+            None => meta,
+        };
+        if !merged.is_empty() && self.meta.get(&evid) != Some(&merged) {
+            self.append(&LogRecordRef::Meta(&evid, &merged))?;
+            self.meta.insert(evid, merged);
+        }
+        Ok(())
+    }
+
+    fn get_signatures(&self, subject: &Hash) -> Result<Signatures, GraphError> {
+        Ok(self.signatures.get(subject).cloned().unwrap_or_default())
+    }
+
+    fn add_signature(
+        &mut self,
+        subject: Hash,
+        signer: SignerId,
+        sig: Signature,
+    ) -> Result<(), GraphError> {
+        let known = self
+            .signatures
+            .get(&subject)
+            .and_then(|sigs| sigs.get(&signer));
+        if known != Some(&sig) {
+            self.append(&LogRecordRef::Signature(&subject, &signer, &sig))?;
+            self.signatures
+                .entry(subject)
+                .or_default()
+                .insert(signer, sig);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{replay_log, Engine, Graph, IncludeSpec, WorkCache};
+    use std::io::Cursor;
+
+    fn ev(arg: &str, deps: &[Hash]) -> Event<String> {
+        Event {
+            cmd: 0,
+            arg: arg.to_string(),
+            deps: deps.iter().map(|&h| (h, true)).collect(),
+        }
+    }
+
+    type Store = FileStore<String, Cursor<Vec<u8>>>;
+
+    #[test]
+    fn roundtrip() {
+        let mut s = Store::create(Cursor::new(Vec::new()), "test", HashAlgo::Blake3)
+            .unwrap()
+            .with_cache_capacity(1);
+        let (_, a) = s.ensure_event(ev("a", &[])).unwrap();
+        let (_, b) = s.ensure_event(ev("b", &[a])).unwrap();
+        assert_eq!(s.ensure_event(ev("a", &[])).unwrap(), (None, a));
+        s.set_nstate("x", [a].into_iter().collect()).unwrap();
+        s.set_nstate("", [b].into_iter().collect()).unwrap();
+        assert!(s.remove_nstate("x").unwrap().is_some());
+        assert_eq!(s.remove_nstate("x").unwrap(), None);
+        let meta = EventMeta {
+            author: Some("alice".to_string()),
+            ..Default::default()
+        };
+        s.merge_meta(a, meta.clone()).unwrap();
+
+        // `a` got evicted from the cache, and has to be read from the file
+        assert_eq!(*s.get_event(&a).unwrap().unwrap(), ev("a", &[]));
+        assert_eq!(*s.get_event(&b).unwrap().unwrap(), ev("b", &[a]));
+
+        let buf = s.into_inner().into_inner();
+        let s = Store::open(Cursor::new(buf.clone()), "test").unwrap();
+        assert_eq!(s.hash_algo(), HashAlgo::Blake3);
+        assert_eq!(s.dropped(), 0);
+        assert_eq!(s.valid_len(), buf.len() as u64);
+        assert_eq!(*s.get_event(&b).unwrap().unwrap(), ev("b", &[a]));
+        assert_eq!(s.nstate_names().unwrap(), vec![String::new()]);
+        assert_eq!(s.get_meta(&a).unwrap(), Some(meta.clone()));
+
+        // the file is a valid event log
+        let mut g = Graph::with_hash_algo(HashAlgo::Blake3);
+        g.ensure_event(ev("a", &[]));
+        g.ensure_event(ev("b", &[a]));
+        g.nstates.insert(String::new(), [b].into_iter().collect());
+        g.meta.insert(a, meta);
+        assert_eq!(replay_log::<String, _>(&buf[..], "test").unwrap().graph, g);
+    }
+
+    #[test]
+    fn truncated() {
+        let mut s = Store::create(Cursor::new(Vec::new()), "test", HashAlgo::default()).unwrap();
+        let (_, a) = s.ensure_event(ev("a", &[])).unwrap();
+        let (_, b) = s.ensure_event(ev("b", &[a])).unwrap();
+        let mut buf = s.into_inner().into_inner();
+        buf.truncate(buf.len() - 3);
+
+        let mut s = Store::open(Cursor::new(buf), "test").unwrap();
+        assert!(s.dropped() > 0);
+        assert!(s.contains_event(&a).unwrap());
+        assert!(!s.contains_event(&b).unwrap());
+        assert_eq!(s.file.get_mut().get_ref().len() as u64, s.valid_len());
+        // a short record mustn't leave stale bytes of `b` behind
+        let (_, c) = s.ensure_event(ev("c", &[])).unwrap();
+
+        let buf = s.into_inner().into_inner();
+        let s = Store::open(Cursor::new(buf), "test").unwrap();
+        assert_eq!(s.dropped(), 0);
+        assert_eq!(*s.get_event(&c).unwrap().unwrap(), ev("c", &[]));
+    }
+
+    struct AppendEngine;
+
+    impl Engine for AppendEngine {
+        type Error = ();
+        type Arg = String;
+        type Dat = String;
+
+        fn run_event_bare(&self, cmd: u32, arg: &String, dat: &String) -> Result<String, ()> {
+            assert_eq!(cmd, 0);
+            Ok(format!("{}{}", dat, arg))
+        }
+    }
+
+    #[test]
+    fn workcache() {
+        let e = AppendEngine;
+        let mut g = Graph::default();
+        let mut s = Store::create(Cursor::new(Vec::new()), "test", HashAlgo::default())
+            .unwrap()
+            .with_cache_capacity(0);
+        let mut wg = WorkCache::new(&e, String::new());
+        let mut ws = WorkCache::new(&e, String::new());
+        let (mut xg, mut xs) = (BTreeSet::new(), BTreeSet::new());
+        for i in ["a", "b", "c"] {
+            let hg = wg.shelve_event(&mut g, xg.clone(), ev(i, &[])).unwrap();
+            let hs = ws.shelve_event(&mut s, xs.clone(), ev(i, &[])).unwrap();
+            assert_eq!(hg, hs);
+            xg.extend(hg);
+            xs.extend(hs);
+        }
+        let (dat, _) = ws
+            .run_foreach_recursively(
+                &s,
+                xs.iter().map(|&h| (h, IncludeSpec::IncludeAll)).collect(),
+            )
+            .unwrap();
+        assert_eq!(dat, "abc");
+    }
+}
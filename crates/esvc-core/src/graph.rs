@@ -1,4 +1,4 @@
-use crate::Hash;
+use crate::{ClosureCache, EventMeta, GraphStore, Hash, HashAlgo, ReflogEntry, Signatures};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -20,12 +20,73 @@ pub enum IncludeSpec {
     IncludeOnlyDeps,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(from = "GraphRepr<Arg>")]
 pub struct Graph<Arg> {
+    /// NOTE: direct modifications of this need to be followed by
+    /// a call to [`Graph::rebuild_dependents`]
     pub events: BTreeMap<Hash, Event<Arg>>,
 
     /// saved combined states
     pub nstates: BTreeMap<String, BTreeSet<Hash>>,
+
+    /// the algorithm used to hash newly inserted events.
+    /// this isn't part of the serialized graph, because that would change
+    /// the data format; after deserialization, it is derived from the hashes
+    /// of the events, so it only needs to be restored for empty graphs.
+    #[serde(skip)]
+    pub hash_algo: HashAlgo,
+
+    /// history of updates of `nstates`, oldest first.
+    /// this isn't part of the serialized graph either,
+    /// graph containers store it separately.
+    #[serde(skip)]
+    pub reflog: Vec<ReflogEntry>,
+
+    /// metadata of events, which is excluded from their hashes.
+    /// stored separately like the reflog, see [`Graph::merge_meta`].
+    #[serde(skip)]
+    pub meta: BTreeMap<Hash, EventMeta>,
+
+    /// signatures of events and states (see [`Graph::state_digest`]),
+    /// stored separately like the reflog
+    #[serde(skip)]
+    pub signatures: BTreeMap<Hash, Signatures>,
+
+    /// reverse dependency index: evid -> (dependent -> is_hard),
+    /// gets rebuilt after deserialization
+    #[serde(skip)]
+    dependents: BTreeMap<Hash, BTreeMap<Hash, bool>>,
+
+    /// the cache of [`Graph::fold_state_memo`], which gets cleared
+    /// together with the reverse dependency index, because it becomes
+    /// invalid once events are removed (e.g. by [`Graph::gc`]).
+    #[serde(skip)]
+    closures: ClosureCache,
+}
+
+/// the serialized part of `Graph<Arg>`
+#[derive(Deserialize)]
+struct GraphRepr<Arg> {
+    events: BTreeMap<Hash, Event<Arg>>,
+    nstates: BTreeMap<String, BTreeSet<Hash>>,
+}
+
+impl<Arg> From<GraphRepr<Arg>> for Graph<Arg> {
+    fn from(repr: GraphRepr<Arg>) -> Self {
+        let mut g = Self {
+            hash_algo: repr
+                .events
+                .keys()
+                .next()
+                .map_or_else(HashAlgo::default, Hash::algo),
+            events: repr.events,
+            nstates: repr.nstates,
+            ..Default::default()
+        };
+        g.rebuild_dependents();
+        g
+    }
 }
 
 impl<Arg> Default for Graph<Arg> {
@@ -33,103 +94,210 @@ impl<Arg> Default for Graph<Arg> {
         Self {
             events: BTreeMap::new(),
             nstates: BTreeMap::new(),
+            hash_algo: HashAlgo::default(),
+            reflog: Vec::new(),
+            meta: BTreeMap::new(),
+            signatures: BTreeMap::new(),
+            dependents: BTreeMap::new(),
+            closures: ClosureCache::new(),
+        }
+    }
+}
+
+// the reverse dependency index is derived from `events`
+impl<Arg: PartialEq> PartialEq for Graph<Arg> {
+    fn eq(&self, other: &Self) -> bool {
+        self.events == other.events
+            && self.nstates == other.nstates
+            && self.hash_algo == other.hash_algo
+            && self.reflog == other.reflog
+            && self.meta == other.meta
+            && self.signatures == other.signatures
+    }
+}
+
+impl<Arg: Eq> Eq for Graph<Arg> {}
+
+impl<Arg> Graph<Arg> {
+    pub fn with_hash_algo(hash_algo: HashAlgo) -> Self {
+        Self {
+            hash_algo,
+            ..Default::default()
         }
     }
 }
 
-#[derive(Clone, Debug, thiserror::Error)]
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
 pub enum GraphError {
     #[error("unable to find the specified dataset")]
     DatasetNotFound,
 
-    #[error("dependency circuit @ {0}")]
-    DependencyCircuit(Hash),
+    /// the path starts and ends with the same event,
+    /// each entry depends on the next one
+    #[error("dependency circuit {}", fmt_path(.0))]
+    DependencyCircuit(Vec<Hash>),
 
     #[error("unable to retrieve dependency {0}")]
     DependencyNotFound(Hash),
 
     // we don't want any dependency on `Arg` here
-    #[error("hash collision @ {0} detected during insertion of {}")]
+    #[error("hash collision @ {0} detected during insertion of {1}")]
     HashCollision(Hash, String),
+
+    /// errors of the storage backend, e.g. I/O errors
+    #[error("storage backend error: {0}")]
+    Storage(String),
 }
 
-impl<Arg: Serialize> Graph<Arg> {
-    /// fold a state, expanding of compressing it along the dependencies.
-    /// `st` entries should be initialized to `false` when creating a state from a `BTreeSet<Hash>`.
-    pub fn fold_state(
-        &self,
-        mut st: BTreeMap<Hash, bool>,
-        expand: bool,
-    ) -> Result<BTreeMap<Hash, bool>, GraphError> {
-        loop {
-            let orig_len = st.len();
-            for (h, _) in st.clone() {
-                match self.events.get(&h) {
-                    Some(x) => st.extend(x.deps.iter().map(|(&j, _)| (j, true))),
-                    None => {
-                        return Err(GraphError::DependencyNotFound(h));
-                    }
-                }
-            }
-            if orig_len == st.len() {
-                break;
-            }
-        }
-        if !expand {
-            // keep only non-dependencies
-            st.retain(|_, is_dep| !*is_dep);
-        }
-        Ok(st)
+pub(crate) fn fmt_path(path: &[Hash]) -> String {
+    path.iter()
+        .map(|h| h.to_string())
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+/// serializes exactly like `Event<Arg>`, but without requiring ownership
+#[derive(Serialize)]
+struct EventView<'a, Arg> {
+    cmd: u32,
+    arg: &'a Arg,
+    deps: &'a BTreeMap<Hash, bool>,
+}
+
+impl<Arg> Graph<Arg> {
+    /// all events which are directly referenced by named states or the reflog
+    fn state_members(&self) -> impl Iterator<Item = &Hash> {
+        self.nstates.values().flatten().chain(
+            self.reflog
+                .iter()
+                .flat_map(|e| e.old.iter().chain(e.new.iter()).flatten()),
+        )
     }
 
-    pub fn calculate_dependencies(
-        &self,
-        mut tt: BTreeSet<Hash>,
-        evids: BTreeMap<Hash, IncludeSpec>,
-    ) -> Result<Vec<Hash>, GraphError> {
-        let mut ret = Vec::new();
+    /// collect all events which are reachable from any named state,
+    /// including previous values of states recorded in the reflog
+    pub fn reachable_events(&self) -> Result<BTreeSet<Hash>, GraphError> {
+        Ok(self
+            .calculate_dependencies(
+                Default::default(),
+                self.state_members()
+                    .map(|&h| (h, IncludeSpec::IncludeAll))
+                    .collect(),
+            )?
+            .into_iter()
+            .collect())
+    }
 
-        // heap of necessary dependencies
-        let mut deps = Vec::new();
+    /// remove all events which aren't reachable from any named state
+    /// (including the reflog, see [`Graph::expire_reflog`]).
+    /// if `dry_run` is set, the graph is left untouched.
+    ///
+    /// @returns the set of (to be) removed events
+    pub fn gc(&mut self, dry_run: bool) -> Result<BTreeSet<Hash>, GraphError> {
+        let keep = self.reachable_events()?;
+        let removed: BTreeSet<Hash> = self
+            .events
+            .keys()
+            .filter(|h| !keep.contains(h))
+            .copied()
+            .collect();
+        if !dry_run {
+            self.events.retain(|h, _| keep.contains(h));
+            // entries of other subjects (e.g. signed states) are kept
+            self.meta.retain(|h, _| !removed.contains(h));
+            self.signatures.retain(|h, _| !removed.contains(h));
+            self.rebuild_dependents();
+        }
+        Ok(removed)
+    }
 
-        for (main_evid, incl) in evids {
-            deps.push(main_evid);
+    /// rehash all events using `algo`, which then becomes the hash algorithm
+    /// of the graph. this happens in topological order, because the references
+    /// to dependencies change, too. `nstates`, the reflog and the metadata
+    /// are updated accordingly. signatures are dropped, because they
+    /// cover the old hashes.
+    /// if an error occurs, the graph is left untouched.
+    ///
+    /// @returns the mapping from old to new hashes
+    pub fn migrate_hash_algo(&mut self, algo: HashAlgo) -> Result<BTreeMap<Hash, Hash>, GraphError>
+    where
+        Arg: esvc_traits::CommandArg,
+    {
+        if let Some(&h) = self.state_members().find(|h| !self.events.contains_key(h)) {
+            return Err(GraphError::DependencyNotFound(h));
+        }
 
-            while let Some(evid) = deps.pop() {
-                if tt.contains(&evid) {
-                    // nothing to do
-                    continue;
-                } else if evid == main_evid && !deps.is_empty() {
-                    return Err(GraphError::DependencyCircuit(main_evid));
-                }
+        let order = self.calculate_dependencies(
+            Default::default(),
+            self.events
+                .keys()
+                .map(|&h| (h, IncludeSpec::IncludeAll))
+                .collect(),
+        )?;
 
-                let evwd = self
-                    .events
-                    .get(&evid)
-                    .ok_or(GraphError::DependencyNotFound(evid))?;
-                let mut necessary_deps = evwd.deps.keys().filter(|&h| !tt.contains(h));
-                if let Some(&x) = necessary_deps.next() {
-                    deps.push(evid);
-                    // TODO: check for dependency cycles
-                    deps.push(x);
-                    deps.extend(necessary_deps.copied());
-                } else {
-                    if evid == main_evid && incl != IncludeSpec::IncludeAll {
-                        // we want to omit the final dep
-                        deps.clear();
-                        break;
+        // calculate the new hashes first, without modifying anything
+        let mut mapping = BTreeMap::new();
+        let mut plan = Vec::with_capacity(order.len());
+        let mut seen = BTreeMap::<Hash, usize>::new();
+        for old in order {
+            let ev = &self.events[&old];
+            let deps: BTreeMap<_, _> = ev
+                .deps
+                .iter()
+                .map(|(dep, &is_hard)| (mapping[dep], is_hard))
+                .collect();
+            let serval = bincode::serialize(&EventView {
+                cmd: ev.cmd,
+                arg: &ev.arg,
+                deps: &deps,
+            })
+            .unwrap();
+            let new = algo.calculate(&serval[..]);
+            match seen.get(&new) {
+                // events which were previously hashed using different algorithms
+                // might now get merged, that's fine.
+                Some(&idx) => {
+                    let (other, _, ref other_deps) = plan[idx];
+                    let other_ev = &self.events[&other];
+                    if other_ev.cmd != ev.cmd || other_ev.arg != ev.arg || other_deps != &deps {
+                        return Err(GraphError::HashCollision(new, format!("{:?}", ev)));
                     }
-                    // run the item, all dependencies are satisfied
-                    ret.push(evid);
-                    tt.insert(evid);
+                }
+                None => {
+                    seen.insert(new, plan.len());
                 }
             }
+            mapping.insert(old, new);
+            plan.push((old, new, deps));
+        }
+
+        let mut old_events = core::mem::take(&mut self.events);
+        for (old, new, deps) in plan {
+            let mut ev = old_events.remove(&old).unwrap();
+            ev.deps = deps;
+            self.events.entry(new).or_insert(ev);
+        }
+        let remap = |st: &mut BTreeSet<Hash>| *st = st.iter().map(|h| mapping[h]).collect();
+        self.nstates.values_mut().for_each(remap);
+        for e in &mut self.reflog {
+            e.old.iter_mut().chain(e.new.iter_mut()).for_each(remap);
         }
-        Ok(ret)
+        for (old, m) in core::mem::take(&mut self.meta) {
+            // metadata of merged events gets merged, too
+            let new = mapping[&old];
+            let m = match self.meta.remove(&new) {
+                Some(prev) => prev.merge(m),
+                None => m,
+            };
+            self.meta.insert(new, m);
+        }
+        self.signatures.clear();
+        self.hash_algo = algo;
+        self.rebuild_dependents();
+
+        Ok(mapping)
     }
-}
 
-impl<Arg> Graph<Arg> {
     /// get-or-insert event, check if it matches
     ///
     /// @returns (Some(@arg ev) if collision else None, Hash of @arg ev)
@@ -138,18 +306,282 @@ impl<Arg> Graph<Arg> {
         Arg: esvc_traits::CommandArg,
     {
         let serval = bincode::serialize::<Event<Arg>>(&ev).unwrap();
-        let h = crate::calculate_hash(&serval[..]);
+        let h = self.hash_algo.calculate(&serval[..]);
         use std::collections::btree_map::Entry;
         (
             match self.events.entry(h) {
                 Entry::Occupied(o) if o.get() == &ev => None,
                 Entry::Occupied(_) => Some(ev),
                 Entry::Vacant(v) => {
-                    v.insert(ev);
+                    index_deps(&mut self.dependents, h, &v.insert(ev).deps);
                     None
                 }
             },
             h,
         )
     }
+
+    /// insert an event without any checks, but keep the reverse dependency index up-to-date
+    pub(crate) fn insert_indexed(&mut self, evid: Hash, ev: Event<Arg>) {
+        index_deps(&mut self.dependents, evid, &ev.deps);
+        self.events.insert(evid, ev);
+    }
+
+    /// recalculate the reverse dependency index and drop the closure cache,
+    /// necessary after modifying `events` directly.
+    pub fn rebuild_dependents(&mut self) {
+        self.closures.clear();
+        self.dependents.clear();
+        for (&h, ev) in &self.events {
+            index_deps(&mut self.dependents, h, &ev.deps);
+        }
+    }
+
+    /// like [`GraphStore::fold_state_cached`], but uses the cache of the graph,
+    /// which is kept valid when events get removed.
+    pub fn fold_state_memo(
+        &mut self,
+        st: BTreeMap<Hash, bool>,
+        expand: bool,
+    ) -> Result<BTreeMap<Hash, bool>, GraphError> {
+        let mut cache = core::mem::take(&mut self.closures);
+        let ret = self.fold_state_cached(st, expand, &mut cache);
+        self.closures = cache;
+        ret
+    }
+
+    /// @returns the events which directly depend on `evid`,
+    /// as dependent -> is_hard
+    pub fn dependents(&self, evid: &Hash) -> &BTreeMap<Hash, bool> {
+        static EMPTY: BTreeMap<Hash, bool> = BTreeMap::new();
+        self.dependents.get(evid).unwrap_or(&EMPTY)
+    }
+
+    /// @returns all events which directly or indirectly depend on `evid`,
+    /// as dependent -> is_hard. an entry is hard if it is connected to
+    /// `evid` via a path consisting only of hard dependencies.
+    pub fn transitive_dependents(&self, evid: &Hash) -> BTreeMap<Hash, bool> {
+        let mut ret = BTreeMap::new();
+        let mut pending = vec![(*evid, true)];
+        while let Some((cur, cur_is_hard)) = pending.pop() {
+            for (&dep, &is_hard) in self.dependents(&cur) {
+                let is_hard = cur_is_hard && is_hard;
+                match ret.get(&dep) {
+                    // nothing new to propagate
+                    Some(&prev) if prev || !is_hard => continue,
+                    _ => {
+                        ret.insert(dep, is_hard);
+                        pending.push((dep, is_hard));
+                    }
+                }
+            }
+        }
+        ret
+    }
+}
+
+fn index_deps(
+    dependents: &mut BTreeMap<Hash, BTreeMap<Hash, bool>>,
+    evid: Hash,
+    deps: &BTreeMap<Hash, bool>,
+) {
+    for (&dep, &is_hard) in deps {
+        dependents.entry(dep).or_default().insert(evid, is_hard);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ev(arg: &str, deps: &[(Hash, bool)]) -> Event<String> {
+        Event {
+            cmd: 0,
+            arg: arg.to_string(),
+            deps: deps.iter().copied().collect(),
+        }
+    }
+
+    #[test]
+    fn gc_unreachable() {
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(ev("a", &[]));
+        let (_, b) = g.ensure_event(ev("b", &[(a, true)]));
+        let (_, c) = g.ensure_event(ev("c", &[(a, false)]));
+        let (_, d) = g.ensure_event(ev("d", &[(c, true)]));
+        g.nstates.insert(String::new(), [b].into_iter().collect());
+        g.merge_meta(b, EventMeta::now()).unwrap();
+        g.merge_meta(d, EventMeta::now()).unwrap();
+        let other = HashAlgo::default().calculate(b"not an event");
+        g.meta.insert(other, EventMeta::now());
+
+        let expected: BTreeSet<_> = [c, d].into_iter().collect();
+        assert_eq!(g.gc(true).unwrap(), expected);
+        assert_eq!(g.events.len(), 4);
+        assert_eq!(g.gc(false).unwrap(), expected);
+        assert_eq!(g.events.len(), 2);
+        assert!(g.events.contains_key(&a) && g.events.contains_key(&b));
+        assert!(g.meta.contains_key(&b) && g.meta.contains_key(&other));
+        assert_eq!(g.meta.len(), 2);
+        assert!(g.gc(false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn closure_cache_invalidation() {
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(ev("a", &[]));
+        let (_, b) = g.ensure_event(ev("b", &[(a, true)]));
+        let st = |hs: &[Hash]| hs.iter().map(|&h| (h, false)).collect();
+        g.fold_state_memo(st(&[b]), false).unwrap();
+        assert_eq!(g.closures.len(), 2);
+
+        // a stale cache would still contain `b` after it got removed
+        g.nstates.insert(String::new(), [a].into_iter().collect());
+        g.gc(false).unwrap();
+        assert!(g.closures.is_empty());
+        assert_eq!(
+            g.fold_state_memo(st(&[b]), false),
+            Err(GraphError::DependencyNotFound(b))
+        );
+
+        g.fold_state_memo(st(&[a]), false).unwrap();
+        g.migrate_hash_algo(HashAlgo::Sha256).unwrap();
+        assert!(g.closures.is_empty());
+    }
+
+    #[test]
+    fn dependents() {
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(ev("a", &[]));
+        let (_, b) = g.ensure_event(ev("b", &[(a, true)]));
+        let (_, c) = g.ensure_event(ev("c", &[(a, false)]));
+        let (_, d) = g.ensure_event(ev("d", &[(b, true), (c, true)]));
+        let (_, e) = g.ensure_event(ev("e", &[(c, true)]));
+
+        let direct: BTreeMap<_, _> = [(b, true), (c, false)].into_iter().collect();
+        assert_eq!(g.dependents(&a), &direct);
+        assert!(g.dependents(&d).is_empty());
+        let trans: BTreeMap<_, _> = [(b, true), (c, false), (d, true), (e, false)]
+            .into_iter()
+            .collect();
+        assert_eq!(g.transitive_dependents(&a), trans);
+
+        // the index isn't serialized, but rebuilt
+        let g2: Graph<String> = bincode::deserialize(&bincode::serialize(&g).unwrap()).unwrap();
+        assert_eq!(g2.dependents, g.dependents);
+
+        g.nstates.insert(String::new(), [b].into_iter().collect());
+        g.gc(false).unwrap();
+        let direct: BTreeMap<_, _> = [(b, true)].into_iter().collect();
+        assert_eq!(g.dependents(&a), &direct);
+        assert!(g.dependents(&c).is_empty());
+    }
+
+    fn assert_circuit(g: &Graph<String>, start: Hash, expected_len: usize) {
+        match g.calculate_dependencies(
+            Default::default(),
+            [(start, IncludeSpec::IncludeAll)].into_iter().collect(),
+        ) {
+            Err(GraphError::DependencyCircuit(c)) => {
+                assert_eq!(c.len(), expected_len, "{:?}", c);
+                assert_eq!(c.first(), c.last());
+                for i in c.windows(2) {
+                    assert!(g.events[&i[0]].deps.contains_key(&i[1]));
+                }
+            }
+            x => panic!("expected dependency circuit, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn hash_algo_per_graph() {
+        let mut g = Graph::with_hash_algo(HashAlgo::Blake3);
+        let (_, a) = g.ensure_event(ev("a", &[]));
+        assert_eq!(a.algo(), HashAlgo::Blake3);
+        let (_, a2) = Graph::default().ensure_event(ev("a", &[]));
+        assert_eq!(a2.algo(), HashAlgo::Blake2b512);
+
+        // the algorithm is derived from the events after deserialization
+        let g2: Graph<String> = bincode::deserialize(&bincode::serialize(&g).unwrap()).unwrap();
+        assert_eq!(g2.hash_algo, HashAlgo::Blake3);
+    }
+
+    #[test]
+    fn migrate_hash_algo() {
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(ev("a", &[]));
+        let (_, b) = g.ensure_event(ev("b", &[(a, true)]));
+        let (_, c) = g.ensure_event(ev("c", &[(a, false), (b, true)]));
+        g.nstates.insert(String::new(), [c].into_iter().collect());
+
+        let mapping = g.migrate_hash_algo(HashAlgo::Sha256).unwrap();
+        assert_eq!(mapping.len(), 3);
+        assert_eq!(g.hash_algo, HashAlgo::Sha256);
+        assert!(mapping.values().all(|h| h.algo() == HashAlgo::Sha256));
+        assert_eq!(g.events.len(), 3);
+        assert_eq!(
+            g.events[&mapping[&c]].deps,
+            [(mapping[&a], false), (mapping[&b], true)]
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(g.nstates[""], [mapping[&c]].into_iter().collect());
+        assert!(g.verify().is_empty());
+
+        // new events now get the same hashes as the migrated ones
+        let (_, a2) = g.ensure_event(ev("a", &[]));
+        assert_eq!(a2, mapping[&a]);
+    }
+
+    #[test]
+    fn migrate_merges_mixed() {
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(ev("a", &[]));
+        g.hash_algo = HashAlgo::Blake3;
+        let (_, a3) = g.ensure_event(ev("a", &[]));
+        assert_ne!(a, a3);
+        let mapping = g.migrate_hash_algo(HashAlgo::Blake3).unwrap();
+        assert_eq!(mapping[&a], a3);
+        assert_eq!(mapping[&a3], a3);
+        assert_eq!(g.events.len(), 1);
+    }
+
+    #[test]
+    fn circuit_intermediate() {
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(ev("a", &[]));
+        let (_, b) = g.ensure_event(ev("b", &[(a, true)]));
+        let (_, c) = g.ensure_event(ev("c", &[(b, true)]));
+        let (_, d) = g.ensure_event(ev("d", &[(c, true)]));
+        // b -> a -> c -> b
+        g.events.get_mut(&a).unwrap().deps.insert(c, false);
+        assert_circuit(&g, d, 4);
+    }
+
+    #[test]
+    fn circuit_self() {
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(ev("a", &[]));
+        let (_, b) = g.ensure_event(ev("b", &[(a, true)]));
+        g.events.get_mut(&a).unwrap().deps.insert(a, true);
+        assert_circuit(&g, b, 2);
+    }
+
+    #[test]
+    fn no_circuit_diamond() {
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(ev("a", &[]));
+        let (_, b) = g.ensure_event(ev("b", &[(a, true)]));
+        let (_, c) = g.ensure_event(ev("c", &[(a, false)]));
+        let (_, d) = g.ensure_event(ev("d", &[(b, true), (c, true)]));
+        let deps = g
+            .calculate_dependencies(
+                Default::default(),
+                [(d, IncludeSpec::IncludeAll)].into_iter().collect(),
+            )
+            .unwrap();
+        assert_eq!(deps.len(), 4);
+        assert_eq!(deps[0], a);
+        assert_eq!(deps[3], d);
+    }
 }
@@ -0,0 +1,232 @@
+use crate::{
+    export::{FnFmt, Selection, STATE_COLOURS},
+    ExportOptions, Graph, GraphError,
+};
+use core::fmt::{self, Formatter, Result, Write};
+use std::collections::BTreeMap;
+
+/// A formatter which can format a graph into GraphML,
+/// for analysis in graph tools.
+///
+/// Nodes carry their label, command and the named states they're part of,
+/// edges carry the `hard` flag. Like with [`Dot`](crate::Dot), formatting
+/// falls back to the whole graph if the selection fails.
+pub struct GraphMl<'a, Arg> {
+    pub graph: &'a Graph<Arg>,
+    pub opts: ExportOptions<'a, Arg>,
+}
+
+impl<'a, Arg> GraphMl<'a, Arg> {
+    pub fn new(graph: &'a Graph<Arg>) -> Self {
+        Self::with_options(graph, ExportOptions::default())
+    }
+
+    pub fn with_options(graph: &'a Graph<Arg>, opts: ExportOptions<'a, Arg>) -> Self {
+        Self { graph, opts }
+    }
+}
+
+const KEYS: &str = r#"  <key id="label" for="node" attr.name="label" attr.type="string"/>
+  <key id="cmd" for="node" attr.name="cmd" attr.type="long"/>
+  <key id="states" for="node" attr.name="states" attr.type="string"/>
+  <key id="colour" for="node" attr.name="colour" attr.type="string"/>
+  <key id="hard" for="edge" attr.name="hard" attr.type="boolean"/>
+  <key id="style" for="edge" attr.name="style" attr.type="string"/>
+  <key id="omitted" for="graph" attr.name="omitted" attr.type="long"/>
+"#;
+
+impl<Arg> GraphMl<'_, Arg> {
+    fn graph_fmt<AF>(&self, f: &mut Formatter<'_>, sel: &Selection, argfmtf: AF) -> Result
+    where
+        AF: Fn(&Arg, &mut Formatter<'_>) -> Result,
+    {
+        let states = sel.states(&self.graph.nstates);
+        // the states of each node (as indices into `states`)
+        let mut node_states = BTreeMap::<_, Vec<usize>>::new();
+        for (idx, (_, deps)) in states.iter().enumerate() {
+            for dep in deps {
+                node_states.entry(*dep).or_default().push(idx);
+            }
+        }
+
+        writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            f,
+            r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+        )?;
+        f.write_str(KEYS)?;
+        writeln!(f, r#"  <graph id="G" edgedefault="directed">"#)?;
+        if sel.omitted != 0 {
+            writeln!(f, r#"    <data key="omitted">{}</data>"#, sel.omitted)?;
+        }
+
+        for h in &sel.nodes {
+            let ev = &self.graph.events[h];
+            writeln!(f, r#"    <node id="{}">"#, Escaped(&sel.names[h]))?;
+            writeln!(
+                f,
+                r#"      <data key="label">{}</data>"#,
+                Escaped(self.opts.label_of(ev, &argfmtf))
+            )?;
+            writeln!(f, r#"      <data key="cmd">{}</data>"#, ev.cmd)?;
+            if let Some(idxs) = node_states.get(h) {
+                let names: Vec<_> = idxs.iter().map(|&i| states[i].0).collect();
+                writeln!(
+                    f,
+                    r#"      <data key="states">{}</data>"#,
+                    Escaped(names.join(" "))
+                )?;
+                if self.opts.state_colours {
+                    writeln!(
+                        f,
+                        r#"      <data key="colour">{}</data>"#,
+                        STATE_COLOURS[idxs[0] % STATE_COLOURS.len()]
+                    )?;
+                }
+            }
+            writeln!(f, "    </node>")?;
+        }
+
+        for h in &sel.nodes {
+            for (dep, &is_hard) in &self.graph.events[h].deps {
+                if !sel.set.contains(dep) {
+                    continue;
+                }
+                writeln!(
+                    f,
+                    r#"    <edge source="{}" target="{}">"#,
+                    Escaped(&sel.names[h]),
+                    Escaped(&sel.names[dep])
+                )?;
+                writeln!(f, r#"      <data key="hard">{}</data>"#, is_hard)?;
+                if self.opts.edge_styles {
+                    writeln!(
+                        f,
+                        r#"      <data key="style">{}</data>"#,
+                        if is_hard { "solid" } else { "dashed" }
+                    )?;
+                }
+                writeln!(f, "    </edge>")?;
+            }
+        }
+
+        writeln!(f, "  </graph>")?;
+        writeln!(f, "</graphml>")
+    }
+}
+
+impl<Arg: fmt::Display> GraphMl<'_, Arg> {
+    /// render the graph, unlike formatting it via `Display`,
+    /// this fails if the selection (see [`ExportOptions::state`]) fails.
+    pub fn render(&self) -> core::result::Result<String, GraphError> {
+        let sel = self.opts.select(self.graph)?;
+        // formatting into a string can't fail once the selection succeeded
+        Ok(FnFmt(&sel, |sel: &Selection, f: &mut Formatter<'_>| {
+            self.graph_fmt(f, sel, fmt::Display::fmt)
+        })
+        .to_string())
+    }
+}
+
+impl<Arg: fmt::Display> fmt::Display for GraphMl<'_, Arg> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let sel = self.opts.select_or_all(self.graph);
+        self.graph_fmt(f, &sel, fmt::Display::fmt)
+    }
+}
+
+impl<Arg: fmt::Debug> fmt::Debug for GraphMl<'_, Arg> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let sel = self.opts.select_or_all(self.graph);
+        self.graph_fmt(f, &sel, fmt::Debug::fmt)
+    }
+}
+
+/// Escape for XML text and attribute values
+struct Escaper<W>(W);
+
+impl<W: fmt::Write> fmt::Write for Escaper<W> {
+    fn write_str(&mut self, s: &str) -> Result {
+        for c in s.chars() {
+            self.write_char(c)?;
+        }
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> Result {
+        match c {
+            '&' => self.0.write_str("&amp;"),
+            '<' => self.0.write_str("&lt;"),
+            '>' => self.0.write_str("&gt;"),
+            '"' => self.0.write_str("&quot;"),
+            '\'' => self.0.write_str("&apos;"),
+            _ => self.0.write_char(c),
+        }
+    }
+}
+
+struct Escaped<T>(T);
+
+impl<T: fmt::Display> fmt::Display for Escaped<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(&mut Escaper(f), "{}", &self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Event, Hash};
+
+    #[test]
+    fn snapshot() {
+        let mut g = Graph::default();
+        let mut ev = |cmd: u32, arg: &str, deps: &[(Hash, bool)]| {
+            g.ensure_event(Event {
+                cmd,
+                arg: arg.to_string(),
+                deps: deps.iter().copied().collect(),
+            })
+            .1
+        };
+        let a = ev(0, "a", &[]);
+        let b = ev(1, "<b>", &[(a, false)]);
+        ev(0, "c", &[(b, true)]);
+        g.nstates
+            .insert("main".to_string(), [b].into_iter().collect());
+        g.nstates.insert("x".to_string(), [b].into_iter().collect());
+
+        let opts = ExportOptions::default().abbrev(true).node_limit(2);
+        let out = GraphMl::with_options(&g, opts).to_string();
+        let expected = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+{keys}  <graph id="G" edgedefault="directed">
+    <data key="omitted">1</data>
+    <node id="IHhRjEo">
+      <data key="label">0:c</data>
+      <data key="cmd">0</data>
+    </node>
+    <node id="JfhNE3J">
+      <data key="label">1:&lt;b&gt;</data>
+      <data key="cmd">1</data>
+      <data key="states">main x</data>
+    </node>
+    <edge source="IHhRjEo" target="JfhNE3J">
+      <data key="hard">true</data>
+    </edge>
+  </graph>
+</graphml>
+"#,
+            keys = KEYS,
+        );
+        assert_eq!(out, expected);
+
+        // formatting falls back to the whole graph
+        let missing = ExportOptions::default().state("nope");
+        assert!(GraphMl::with_options(&g, missing).render().is_err());
+        let missing = ExportOptions::default().state("nope");
+        let out = GraphMl::with_options(&g, missing).to_string();
+        assert_eq!(out.matches("<node ").count(), 3);
+    }
+}
@@ -11,17 +11,129 @@ use std::fmt;
 #[derive(Clone, Copy, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Hash {
     Blake2b512(#[serde_as(as = "serde_with::Bytes")] [u8; 64]),
+    Blake3(#[serde_as(as = "serde_with::Bytes")] [u8; 32]),
+    Sha256(#[serde_as(as = "serde_with::Bytes")] [u8; 32]),
 }
 
-const HASH_B64_CFG: base64::Config = base64::Config::new(base64::CharacterSet::UrlSafe, false);
+/// The algorithm which is used to calculate a [`Hash`].
+///
+/// The shorter variants reduce the size of serialized graphs, because
+/// every dependency reference carries a full hash. They don't reduce
+/// memory usage: [`Hash`] is always as large as its largest variant
+/// (65 bytes), thus e.g. the keys of [`WorkCache`](crate::WorkCache)
+/// don't shrink.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum HashAlgo {
+    #[default]
+    Blake2b512,
+    Blake3,
+    Sha256,
+}
+
+pub(crate) const HASH_B64_CFG: base64::Config =
+    base64::Config::new(base64::CharacterSet::UrlSafe, false);
 const HASH_BLK2512_PFX: &str = "blake2b512:";
+const HASH_BLK3_PFX: &str = "blake3:";
+const HASH_SHA256_PFX: &str = "sha256:";
+
+impl HashAlgo {
+    pub const ALL: [HashAlgo; 3] = [HashAlgo::Blake2b512, HashAlgo::Blake3, HashAlgo::Sha256];
+
+    /// the prefix used in the string representation of hashes
+    pub fn prefix(self) -> &'static str {
+        match self {
+            HashAlgo::Blake2b512 => HASH_BLK2512_PFX,
+            HashAlgo::Blake3 => HASH_BLK3_PFX,
+            HashAlgo::Sha256 => HASH_SHA256_PFX,
+        }
+    }
+
+    /// the length of the resulting hash in bytes
+    pub fn digest_len(self) -> usize {
+        match self {
+            HashAlgo::Blake2b512 => 64,
+            HashAlgo::Blake3 | HashAlgo::Sha256 => 32,
+        }
+    }
+
+    pub fn calculate(self, dat: &[u8]) -> Hash {
+        match self {
+            HashAlgo::Blake2b512 => {
+                use blake2::Digest;
+                let mut hasher = blake2::Blake2b512::new();
+                hasher.update(dat);
+                let tmp = hasher.finalize();
+                let mut ret = [0u8; 64];
+                ret.copy_from_slice(tmp.as_slice());
+                Hash::Blake2b512(ret)
+            }
+            HashAlgo::Blake3 => Hash::Blake3(*blake3::hash(dat).as_bytes()),
+            HashAlgo::Sha256 => {
+                use sha2::Digest;
+                let mut hasher = sha2::Sha256::new();
+                hasher.update(dat);
+                let tmp = hasher.finalize();
+                let mut ret = [0u8; 32];
+                ret.copy_from_slice(tmp.as_slice());
+                Hash::Sha256(ret)
+            }
+        }
+    }
+}
+
+impl fmt::Display for HashAlgo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.prefix().trim_end_matches(':'))
+    }
+}
+
+impl core::str::FromStr for HashAlgo {
+    type Err = HashDecodeError;
+
+    fn from_str(s: &str) -> Result<HashAlgo, HashDecodeError> {
+        HashAlgo::ALL
+            .into_iter()
+            .find(|a| a.prefix().trim_end_matches(':') == s)
+            .ok_or_else(|| HashDecodeError::InvalidPrefix(s.to_string()))
+    }
+}
+
+impl Hash {
+    pub fn algo(&self) -> HashAlgo {
+        match self {
+            Hash::Blake2b512(_) => HashAlgo::Blake2b512,
+            Hash::Blake3(_) => HashAlgo::Blake3,
+            Hash::Sha256(_) => HashAlgo::Sha256,
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Hash::Blake2b512(x) => &x[..],
+            Hash::Blake3(x) | Hash::Sha256(x) => &x[..],
+        }
+    }
+
+    /// @returns `None` if `bytes` has the wrong length for `algo`
+    pub fn from_bytes(algo: HashAlgo, bytes: &[u8]) -> Option<Hash> {
+        Some(match algo {
+            HashAlgo::Blake2b512 => Hash::Blake2b512(bytes.try_into().ok()?),
+            HashAlgo::Blake3 => Hash::Blake3(bytes.try_into().ok()?),
+            HashAlgo::Sha256 => Hash::Sha256(bytes.try_into().ok()?),
+        })
+    }
+}
 
 impl fmt::Display for Hash {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (kind, bytes) = match self {
-            Hash::Blake2b512(ref x) => (HASH_BLK2512_PFX, x),
-        };
-        write!(f, "{}{}", kind, base64::encode_config(bytes, HASH_B64_CFG))
+        write!(
+            f,
+            "{}{}",
+            self.algo().prefix(),
+            base64::encode_config(self.as_bytes(), HASH_B64_CFG)
+        )
     }
 }
 
@@ -40,6 +152,9 @@ pub enum HashDecodeError {
     #[error("concrete hash part is too short (got {got}, expected {expected})")]
     TooShort { got: usize, expected: usize },
 
+    #[error("concrete hash part is too long (got {got}, expected {expected})")]
+    TooLong { got: usize, expected: usize },
+
     #[error("invalid hash prefix '{0}'")]
     InvalidPrefix(String),
 }
@@ -48,40 +163,35 @@ impl core::str::FromStr for Hash {
     type Err = HashDecodeError;
 
     fn from_str(s: &str) -> Result<Hash, HashDecodeError> {
-        if let Some(x) = s.strip_prefix(HASH_BLK2512_PFX) {
-            let mut buf = [0u8; 64];
-            let dcl = base64::decode_config_slice(x, HASH_B64_CFG, &mut buf).map_err(|x| {
-                use base64::DecodeError as Bdce;
-                let offset = HASH_BLK2512_PFX.len();
-                match x {
-                    Bdce::InvalidByte(a, b) => Bdce::InvalidByte(offset + a, b),
-                    Bdce::InvalidLength => Bdce::InvalidLength,
-                    Bdce::InvalidLastSymbol(a, b) => Bdce::InvalidLastSymbol(offset + a, b),
-                }
+        let (algo, x) = HashAlgo::ALL
+            .into_iter()
+            .find_map(|algo| s.strip_prefix(algo.prefix()).map(|x| (algo, x)))
+            .ok_or_else(|| {
+                let truncp = s.find(':').unwrap_or(s.len());
+                HashDecodeError::InvalidPrefix(s[..truncp].to_string())
             })?;
-            if dcl < buf.len() {
-                return Err(HashDecodeError::TooShort {
-                    got: x.len(),
-                    expected: buf.len(),
-                });
+        let buf = base64::decode_config(x, HASH_B64_CFG).map_err(|x| {
+            use base64::DecodeError as Bdce;
+            let offset = algo.prefix().len();
+            match x {
+                Bdce::InvalidByte(a, b) => Bdce::InvalidByte(offset + a, b),
+                Bdce::InvalidLength => Bdce::InvalidLength,
+                Bdce::InvalidLastSymbol(a, b) => Bdce::InvalidLastSymbol(offset + a, b),
             }
-            Ok(Hash::Blake2b512(buf))
-        } else {
-            let truncp = s.find(':').unwrap_or(s.len());
-            Err(HashDecodeError::InvalidPrefix(s[..truncp].to_string()))
+        })?;
+        let expected = algo.digest_len();
+        match buf.len() {
+            got if got < expected => Err(HashDecodeError::TooShort { got, expected }),
+            got if got > expected => Err(HashDecodeError::TooLong { got, expected }),
+            _ => Ok(Hash::from_bytes(algo, &buf[..]).unwrap()),
         }
     }
 }
 
-// TODO: make it possible to select which hash should be used
+/// calculate the hash of `dat` using the default algorithm,
+/// see also [`HashAlgo::calculate`].
 pub fn calculate_hash(dat: &[u8]) -> Hash {
-    use blake2::Digest;
-    let mut hasher = blake2::Blake2b512::new();
-    hasher.update(dat);
-    let tmp = hasher.finalize();
-    let mut ret = [0u8; 64];
-    ret.copy_from_slice(tmp.as_slice());
-    Hash::Blake2b512(ret)
+    HashAlgo::default().calculate(dat)
 }
 
 #[cfg(test)]
@@ -125,4 +235,54 @@ mod tests {
         assert_eq!(GTH.to_string(), GTH_STR);
         assert_eq!(GTH_STR.parse::<Hash>(), Ok(GTH));
     }
+
+    #[test]
+    fn hash_parse_err_length() {
+        assert_eq!(
+            "sha256:AAAA".parse::<Hash>(),
+            Err(HashDecodeError::TooShort {
+                got: 3,
+                expected: 32
+            })
+        );
+        assert_eq!(
+            format!("sha256:{}", &GTH_STR[HASH_BLK2512_PFX.len()..]).parse::<Hash>(),
+            Err(HashDecodeError::TooLong {
+                got: 64,
+                expected: 32
+            })
+        );
+    }
+
+    #[test]
+    fn blake3_empty() {
+        let h = HashAlgo::Blake3.calculate(&[]);
+        assert_eq!(
+            h.as_bytes(),
+            &[
+                0xaf, 0x13, 0x49, 0xb9, 0xf5, 0xf9, 0xa1, 0xa6, 0xa0, 0x40, 0x4d, 0xea, 0x36, 0xdc,
+                0xc9, 0x49, 0x9b, 0xcb, 0x25, 0xc9, 0xad, 0xc1, 0x12, 0xb7, 0xcc, 0x9a, 0x93, 0xca,
+                0xe4, 0x1f, 0x32, 0x62,
+            ][..]
+        );
+        assert_eq!(h.to_string().parse::<Hash>(), Ok(h));
+    }
+
+    const GTH_SHA256_STR: &str = "sha256:ANmzfa6ZZPnGtjHI-TMOi2F3EJu_up-vf3U3zQdNV0w";
+
+    #[test]
+    fn ex0_sha256() {
+        let h = HashAlgo::Sha256.calculate("Guten Tag!".as_bytes());
+        assert_eq!(h.algo(), HashAlgo::Sha256);
+        assert_eq!(h.to_string(), GTH_SHA256_STR);
+        assert_eq!(GTH_SHA256_STR.parse::<Hash>(), Ok(h));
+    }
+
+    #[test]
+    fn algo_str() {
+        for algo in HashAlgo::ALL {
+            assert_eq!(algo.to_string().parse::<HashAlgo>(), Ok(algo));
+            assert_eq!(algo.calculate(&[]).as_bytes().len(), algo.digest_len());
+        }
+    }
 }
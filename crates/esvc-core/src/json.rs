@@ -0,0 +1,176 @@
+use crate::{export::Selection, ExportOptions, Graph, GraphError};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A JSON document describing (a part of) a graph, see [`Json`].
+// NOTE: the names and order of the fields are part of the data format
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct JsonGraph<Arg> {
+    /// dependents come before their dependencies
+    pub events: Vec<JsonEvent<Arg>>,
+    pub nstates: BTreeMap<String, Vec<String>>,
+    /// the number of events left out because of the node limit
+    pub omitted: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct JsonEvent<Arg> {
+    pub id: String,
+    pub cmd: u32,
+    pub arg: Arg,
+    /// only present if a custom label was set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// `true` for hard dependencies
+    pub deps: BTreeMap<String, bool>,
+}
+
+/// A formatter which can format a graph into a stable JSON document.
+///
+/// The edge style and colour options don't apply to this format.
+/// Like with [`Dot`](crate::Dot), formatting falls back to the whole graph
+/// if the selection fails.
+pub struct Json<'a, Arg> {
+    pub graph: &'a Graph<Arg>,
+    pub opts: ExportOptions<'a, Arg>,
+}
+
+impl<'a, Arg> Json<'a, Arg> {
+    pub fn new(graph: &'a Graph<Arg>) -> Self {
+        Self::with_options(graph, ExportOptions::default())
+    }
+
+    pub fn with_options(graph: &'a Graph<Arg>, opts: ExportOptions<'a, Arg>) -> Self {
+        Self { graph, opts }
+    }
+
+    /// build the document, this fails if the selection
+    /// (see [`ExportOptions::state`]) fails
+    pub fn document(&self) -> Result<JsonGraph<&'a Arg>, GraphError> {
+        Ok(self.document_of(&self.opts.select(self.graph)?))
+    }
+
+    fn document_of(&self, sel: &Selection) -> JsonGraph<&'a Arg> {
+        let events = sel
+            .nodes
+            .iter()
+            .map(|h| {
+                let ev = &self.graph.events[h];
+                JsonEvent {
+                    id: sel.names[h].clone(),
+                    cmd: ev.cmd,
+                    arg: &ev.arg,
+                    label: self.opts.label.as_ref().map(|labelf| labelf(ev)),
+                    deps: ev
+                        .deps
+                        .iter()
+                        .filter(|(dep, _)| sel.set.contains(dep))
+                        .map(|(dep, &is_hard)| (sel.names[dep].clone(), is_hard))
+                        .collect(),
+                }
+            })
+            .collect();
+        let nstates = sel
+            .states(&self.graph.nstates)
+            .into_iter()
+            .map(|(name, deps)| {
+                let deps = deps.iter().map(|h| sel.names[h].clone()).collect();
+                (name.to_string(), deps)
+            })
+            .collect();
+        JsonGraph {
+            events,
+            nstates,
+            omitted: sel.omitted,
+        }
+    }
+}
+
+impl<Arg: Serialize> fmt::Display for Json<'_, Arg> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let doc = self.document_of(&self.opts.select_or_all(self.graph));
+        let s = serde_json::to_string_pretty(&doc).map_err(|_| fmt::Error)?;
+        writeln!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Event, Hash};
+
+    #[test]
+    fn snapshot() {
+        let mut g = Graph::default();
+        let mut ev = |arg: &str, deps: &[(Hash, bool)]| {
+            g.ensure_event(Event {
+                cmd: 0,
+                arg: arg.to_string(),
+                deps: deps.iter().copied().collect(),
+            })
+            .1
+        };
+        let a = ev("a", &[]);
+        let b = ev("b", &[(a, false)]);
+        let c = ev("c", &[(a, true)]);
+        g.nstates
+            .insert("main".to_string(), [b, c].into_iter().collect());
+
+        let opts = ExportOptions::default()
+            .abbrev(true)
+            .label(|ev: &Event<String>| ev.arg.to_uppercase());
+        let out = Json::with_options(&g, opts).to_string();
+        let expected = r#"{
+  "events": [
+    {
+      "id": "P_9dA1M",
+      "cmd": 0,
+      "arg": "c",
+      "label": "C",
+      "deps": {
+        "y06qQbZ": true
+      }
+    },
+    {
+      "id": "L-APvKI",
+      "cmd": 0,
+      "arg": "b",
+      "label": "B",
+      "deps": {
+        "y06qQbZ": false
+      }
+    },
+    {
+      "id": "y06qQbZ",
+      "cmd": 0,
+      "arg": "a",
+      "label": "A",
+      "deps": {}
+    }
+  ],
+  "nstates": {
+    "main": [
+      "L-APvKI",
+      "P_9dA1M"
+    ]
+  },
+  "omitted": 0
+}
+"#;
+        assert_eq!(out, expected);
+
+        // the document can be read back
+        let doc: JsonGraph<String> = serde_json::from_str(&out).unwrap();
+        assert_eq!(doc.events.len(), 3);
+        assert_eq!(doc.nstates["main"].len(), 2);
+
+        // formatting falls back to the whole graph
+        let missing = ExportOptions::default().state("nope");
+        assert!(Json::with_options(&g, missing).document().is_err());
+        let missing = ExportOptions::default().state("nope");
+        let out = Json::with_options(&g, missing).to_string();
+        let doc: JsonGraph<String> = serde_json::from_str(&out).unwrap();
+        assert_eq!(doc.events.len(), 3);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{hash::HASH_B64_CFG, SignerId};
+use ed25519_dalek::SigningKey;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::Path;
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeyError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    #[error("unable to get random bytes: {0}")]
+    Random(getrandom::Error),
+
+    #[error("invalid key file, line {line}: {msg}")]
+    Invalid { line: usize, msg: String },
+}
+
+/// generate a new signing key and store it in a new file at `path`,
+/// which is only readable by the current user (on unix).
+/// an existing file is never overwritten.
+pub fn generate_key_file(path: &Path) -> Result<SigningKey, KeyError> {
+    let mut seed = [0u8; 32];
+    getrandom::getrandom(&mut seed).map_err(KeyError::Random)?;
+    let key = SigningKey::from_bytes(&seed);
+
+    let mut opts = std::fs::OpenOptions::new();
+    opts.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut opts, 0o600);
+    let mut f = opts.open(path)?;
+    writeln!(f, "{}", base64::encode_config(seed, HASH_B64_CFG))?;
+    f.sync_all()?;
+    Ok(key)
+}
+
+/// read a signing key written by [`generate_key_file`]
+pub fn read_key_file(path: &Path) -> Result<SigningKey, KeyError> {
+    let dat = std::fs::read_to_string(path)?;
+    let invalid = |msg: &str| KeyError::Invalid {
+        line: 1,
+        msg: msg.to_string(),
+    };
+    let seed =
+        base64::decode_config(dat.trim(), HASH_B64_CFG).map_err(|e| invalid(&e.to_string()))?;
+    let seed: [u8; 32] = seed.try_into().map_err(|_| invalid("wrong key length"))?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+/// The set of trusted public keys, with a name for each of them.
+///
+/// The file format consists of one key per line, followed by the name;
+/// empty lines and lines starting with `#` are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Keyring {
+    pub keys: BTreeMap<SignerId, String>,
+}
+
+impl Keyring {
+    pub fn insert(&mut self, key: SignerId, name: String) {
+        self.keys.insert(key, name);
+    }
+
+    pub fn trusts(&self, key: &SignerId) -> bool {
+        self.keys.contains_key(key)
+    }
+
+    pub fn name_of(&self, key: &SignerId) -> Option<&str> {
+        self.keys.get(key).map(|x| &x[..])
+    }
+
+    pub fn parse(dat: &str) -> Result<Self, KeyError> {
+        let mut ret = Self::default();
+        for (lnum, line) in dat.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, name) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let key = key
+                .parse()
+                .map_err(|e: base64::DecodeError| KeyError::Invalid {
+                    line: lnum + 1,
+                    msg: e.to_string(),
+                })?;
+            ret.insert(key, name.trim().to_string());
+        }
+        Ok(ret)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, KeyError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyring() {
+        let alice = SignerId::from(&SigningKey::from_bytes(&[1; 32]).verifying_key());
+        let dat = format!("# trusted keys\n\n{} alice a.\n", alice);
+        let kr = Keyring::parse(&dat).unwrap();
+        assert!(kr.trusts(&alice));
+        assert_eq!(kr.name_of(&alice), Some("alice a."));
+        assert!(matches!(
+            Keyring::parse("\nfoo bar"),
+            Err(KeyError::Invalid { line: 2, .. })
+        ));
+    }
+}
@@ -1,6 +1,13 @@
 #![forbid(unsafe_code)]
 
 pub use bincode;
+pub use serde_json;
+
+#[cfg(feature = "signatures")]
+pub use ed25519_dalek;
+
+#[cfg(feature = "rusqlite")]
+pub use rusqlite;
 
 #[doc(no_inline)]
 pub use esvc_traits::Engine;
@@ -8,11 +15,83 @@ pub use esvc_traits::Engine;
 mod hash;
 pub use hash::*;
 
+mod abbrev;
+pub use abbrev::*;
+
 mod graph;
 pub use graph::*;
 
+mod store;
+pub use store::*;
+
+mod container;
+pub use container::*;
+
+mod bundle;
+pub use bundle::*;
+
 mod dot;
 pub use dot::*;
 
+mod evlog;
+pub use evlog::*;
+
+mod export;
+pub use export::*;
+
+mod filestore;
+pub use filestore::*;
+
+mod graphml;
+pub use graphml::*;
+
+mod json;
+pub use json::*;
+
+#[cfg(feature = "rusqlite")]
+mod sqlite;
+#[cfg(feature = "rusqlite")]
+pub use sqlite::*;
+
+#[cfg(feature = "signatures")]
+mod keys;
+#[cfg(feature = "signatures")]
+pub use keys::*;
+
+mod loggraph;
+pub use loggraph::*;
+
+mod mermaid;
+pub use mermaid::*;
+
+mod meta;
+pub use meta::*;
+
+mod patch;
+pub use patch::*;
+
+mod query;
+
+mod refs;
+pub use refs::*;
+
+mod reflog;
+pub use reflog::*;
+
+mod revset;
+pub use revset::*;
+
+mod signature;
+pub use signature::*;
+
+mod stats;
+pub use stats::*;
+
+mod sync;
+pub use sync::*;
+
+mod verify;
+pub use verify::*;
+
 mod workcache;
 pub use workcache::*;
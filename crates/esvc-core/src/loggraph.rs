@@ -0,0 +1,224 @@
+use crate::{export::Selection, ExportOptions, Graph, GraphError, Hash};
+use core::fmt;
+
+/// A text renderer which lays out the graph as lanes,
+/// similar to `git log --graph`, without needing Graphviz.
+///
+/// Each row shows an event (`*`) with its (abbreviated) hash, `cmd` and
+/// a one-line summary, dependents come before their dependencies.
+/// Lanes of hard dependencies are drawn as `|`, those of soft ones as `:`.
+/// A custom label (see [`ExportOptions::label`]) replaces the summary,
+/// the edge style and colour options don't apply.
+pub struct LogGraph<'a, Arg> {
+    pub graph: &'a Graph<Arg>,
+    pub opts: ExportOptions<'a, Arg>,
+}
+
+/// a lane points to the event it is waiting for
+type Lane = Option<(Hash, bool)>;
+
+fn free_lane(lanes: &mut Vec<Lane>, from: usize) -> usize {
+    match lanes.iter().skip(from).position(Option::is_none) {
+        Some(i) => from + i,
+        None => {
+            lanes.push(None);
+            lanes.len() - 1
+        }
+    }
+}
+
+fn draw_lanes(lanes: &[Lane]) -> Vec<char> {
+    let mut row = vec![' '; 2 * lanes.len()];
+    for (i, lane) in lanes.iter().enumerate() {
+        row[2 * i] = match lane {
+            Some((_, true)) => '|',
+            Some((_, false)) => ':',
+            None => ' ',
+        };
+    }
+    row
+}
+
+/// connect lane `from` to lane `to` (`from < to`), crossing other lanes
+fn connect(row: &mut [char], from: usize, to: usize, end: char) {
+    for c in &mut row[2 * from + 1..2 * to] {
+        *c = match *c {
+            ' ' => '-',
+            '|' | ':' => '+',
+            x => x,
+        };
+    }
+    row[2 * to] = end;
+}
+
+fn push_row(out: &mut String, row: &[char]) {
+    let row: String = row.iter().collect();
+    out.push_str(row.trim_end());
+    out.push('\n');
+}
+
+impl<'a, Arg> LogGraph<'a, Arg> {
+    pub fn new(graph: &'a Graph<Arg>) -> Self {
+        Self::with_options(graph, ExportOptions::default())
+    }
+
+    pub fn with_options(graph: &'a Graph<Arg>, opts: ExportOptions<'a, Arg>) -> Self {
+        Self { graph, opts }
+    }
+
+    /// render the graph, `summary` is used to describe the argument of
+    /// each event, only the first line of it is shown.
+    /// unlike formatting via `Display`, this fails if the selection
+    /// (see [`ExportOptions::state`]) fails.
+    pub fn render<F>(&self, summary: F) -> Result<String, GraphError>
+    where
+        F: Fn(&Arg) -> String,
+    {
+        Ok(self.render_selection(&self.opts.select(self.graph)?, summary))
+    }
+
+    fn render_selection<F>(&self, sel: &Selection, summary: F) -> String
+    where
+        F: Fn(&Arg) -> String,
+    {
+        let mut lanes: Vec<Lane> = Vec::new();
+        let mut out = String::new();
+
+        for h in &sel.nodes {
+            let ev = &self.graph.events[h];
+            let waiting: Vec<usize> = lanes
+                .iter()
+                .enumerate()
+                .filter(|(_, lane)| matches!(lane, Some((x, _)) if x == h))
+                .map(|(i, _)| i)
+                .collect();
+            let col = match waiting.first() {
+                Some(&col) => col,
+                None => free_lane(&mut lanes, 0),
+            };
+
+            // join the other lanes which end at this event
+            if waiting.len() > 1 {
+                let mut row = draw_lanes(&lanes);
+                for &j in &waiting[1..] {
+                    connect(&mut row, col, j, '\'');
+                    lanes[j] = None;
+                }
+                push_row(&mut out, &row);
+                while lanes.last() == Some(&None) {
+                    lanes.pop();
+                }
+            }
+
+            let mut row = draw_lanes(&lanes);
+            row[2 * col] = '*';
+            let text = match &self.opts.label {
+                Some(labelf) => labelf(ev),
+                None => summary(&ev.arg),
+            };
+            let row: String = row.into_iter().collect();
+            out.push_str(&format!(
+                "{}{} {} {}",
+                row,
+                sel.names[h],
+                ev.cmd,
+                text.lines().next().unwrap_or("")
+            ));
+            out.truncate(out.trim_end().len());
+            out.push('\n');
+
+            // fork off lanes for the dependencies
+            let mut deps = ev
+                .deps
+                .iter()
+                .filter(|(dep, _)| sel.set.contains(dep))
+                .map(|(&dep, &is_hard)| (dep, is_hard));
+            lanes[col] = deps.next();
+            let mut forks = Vec::new();
+            for dep in deps {
+                let k = free_lane(&mut lanes, col + 1);
+                lanes[k] = Some(dep);
+                forks.push(k);
+            }
+            if !forks.is_empty() {
+                let mut row = draw_lanes(&lanes);
+                for k in forks {
+                    connect(&mut row, col, k, '.');
+                }
+                push_row(&mut out, &row);
+            }
+            while lanes.last() == Some(&None) {
+                lanes.pop();
+            }
+        }
+
+        if sel.omitted != 0 {
+            out.push_str(&format!("~ {} more events\n", sel.omitted));
+        }
+        out
+    }
+}
+
+// like the other exporters, this falls back to the whole graph
+// (in no particular order) if the selection fails
+impl<Arg: fmt::Display> fmt::Display for LogGraph<'_, Arg> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sel = self.opts.select_or_all(self.graph);
+        f.write_str(&self.render_selection(&sel, |arg| arg.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Event;
+
+    #[test]
+    fn lanes() {
+        let mut g = Graph::default();
+        let mut ev = |cmd: u32, arg: &str, deps: &[(Hash, bool)]| {
+            g.ensure_event(Event {
+                cmd,
+                arg: arg.to_string(),
+                deps: deps.iter().copied().collect(),
+            })
+            .1
+        };
+        let a = ev(0, "a\nsecond line", &[]);
+        let b = ev(1, "b", &[(a, true)]);
+        let c = ev(0, "c", &[(a, false)]);
+        let d = ev(2, "d", &[(b, true), (c, false)]);
+        ev(3, "e", &[(c, true)]);
+        g.nstates
+            .insert("main".to_string(), [d].into_iter().collect());
+
+        let opts = ExportOptions::default().state("main").abbrev(true);
+        let out = LogGraph::with_options(&g, opts).to_string();
+        assert_eq!(
+            out,
+            "* kUVO5Zv 2 d\n:-.\n* | cInLVF4 0 c\n: * 9Z2mgX3 1 b\n:-'\n* f06Y5z5 0 a\n"
+        );
+
+        // unrelated heads get their own lane
+        let opts = ExportOptions::default()
+            .abbrev(true)
+            .node_limit(4)
+            .label(|ev: &Event<String>| ev.arg.to_uppercase());
+        let out = LogGraph::with_options(&g, opts).to_string();
+        assert_eq!(
+            out,
+            "* kUVO5Zv 2 D\n:-.\n: * 9Z2mgX3 1 B\n: * dGk0kfB 3 E\n:-'\n* cInLVF4 0 C\n~ 1 more events\n"
+        );
+
+        // formatting falls back to the whole graph, even if it contains a cycle
+        let a = g.events.keys().next().copied().unwrap();
+        g.events.get_mut(&a).unwrap().deps.insert(a, true);
+        g.rebuild_dependents();
+        let opts = ExportOptions::default().state("nope");
+        assert!(LogGraph::with_options(&g, opts)
+            .render(|arg| arg.clone())
+            .is_err());
+        let out = LogGraph::new(&g).to_string();
+        assert_eq!(out.matches('*').count(), 5);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{
+    export::{FnFmt, Selection, STATE_COLOURS},
+    ExportOptions, Graph, GraphError,
+};
+use core::fmt::{self, Formatter, Result, Write};
+
+/// A formatter which can format a graph into a Mermaid flowchart,
+/// which can be embedded into markdown documents.
+///
+/// Named states are rendered as separate nodes which are linked
+/// to their members. Like with [`Dot`](crate::Dot), formatting falls
+/// back to the whole graph if the selection fails.
+pub struct Mermaid<'a, Arg> {
+    pub graph: &'a Graph<Arg>,
+    pub opts: ExportOptions<'a, Arg>,
+}
+
+impl<'a, Arg> Mermaid<'a, Arg> {
+    pub fn new(graph: &'a Graph<Arg>) -> Self {
+        Self::with_options(graph, ExportOptions::default())
+    }
+
+    pub fn with_options(graph: &'a Graph<Arg>, opts: ExportOptions<'a, Arg>) -> Self {
+        Self { graph, opts }
+    }
+}
+
+impl<Arg> Mermaid<'_, Arg> {
+    fn graph_fmt<AF>(&self, f: &mut Formatter<'_>, sel: &Selection, argfmtf: AF) -> Result
+    where
+        AF: Fn(&Arg, &mut Formatter<'_>) -> Result,
+    {
+        // hashes might contain characters which aren't allowed in node ids
+        let ids: std::collections::BTreeMap<_, _> = sel
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, h)| (*h, format!("e{}", i)))
+            .collect();
+        writeln!(f, "flowchart TD")?;
+
+        for h in &sel.nodes {
+            let label = self.opts.label_of(&self.graph.events[h], &argfmtf);
+            writeln!(
+                f,
+                "  {}[\"{}<br/>{}\"]",
+                ids[h],
+                Escaped(&sel.names[h]),
+                Escaped(label)
+            )?;
+        }
+        if sel.omitted != 0 {
+            writeln!(f, "  more[\"{} more events\"]", sel.omitted)?;
+        }
+
+        for h in &sel.nodes {
+            for (dep, &is_hard) in &self.graph.events[h].deps {
+                if !sel.set.contains(dep) {
+                    continue;
+                }
+                let arrow = match (self.opts.edge_styles, is_hard) {
+                    (true, true) => "-->",
+                    (true, false) => "-.->",
+                    (false, true) => "-->|hard|",
+                    (false, false) => "-->|soft|",
+                };
+                writeln!(f, "  {} {} {}", ids[h], arrow, ids[dep])?;
+            }
+        }
+
+        for (idx, (nstate, deps)) in sel.states(&self.graph.nstates).into_iter().enumerate() {
+            writeln!(f, "  s{}([\"{}\"])", idx, Escaped(nstate))?;
+            for dep in deps {
+                writeln!(f, "  s{} -.- {}", idx, ids[&dep])?;
+            }
+            if self.opts.state_colours {
+                writeln!(
+                    f,
+                    "  style s{} fill:{}",
+                    idx,
+                    STATE_COLOURS[idx % STATE_COLOURS.len()]
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<Arg: fmt::Display> Mermaid<'_, Arg> {
+    /// render the graph, unlike formatting it via `Display`,
+    /// this fails if the selection (see [`ExportOptions::state`]) fails.
+    pub fn render(&self) -> core::result::Result<String, GraphError> {
+        let sel = self.opts.select(self.graph)?;
+        // formatting into a string can't fail once the selection succeeded
+        Ok(FnFmt(&sel, |sel: &Selection, f: &mut Formatter<'_>| {
+            self.graph_fmt(f, sel, fmt::Display::fmt)
+        })
+        .to_string())
+    }
+}
+
+impl<Arg: fmt::Display> fmt::Display for Mermaid<'_, Arg> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let sel = self.opts.select_or_all(self.graph);
+        self.graph_fmt(f, &sel, fmt::Display::fmt)
+    }
+}
+
+impl<Arg: fmt::Debug> fmt::Debug for Mermaid<'_, Arg> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let sel = self.opts.select_or_all(self.graph);
+        self.graph_fmt(f, &sel, fmt::Debug::fmt)
+    }
+}
+
+/// Escape for quoted Mermaid labels
+struct Escaper<W>(W);
+
+impl<W: fmt::Write> fmt::Write for Escaper<W> {
+    fn write_str(&mut self, s: &str) -> Result {
+        for c in s.chars() {
+            self.write_char(c)?;
+        }
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> Result {
+        match c {
+            '"' => self.0.write_str("#quot;"),
+            '<' => self.0.write_str("#lt;"),
+            '>' => self.0.write_str("#gt;"),
+            '#' => self.0.write_str("#35;"),
+            '\n' => self.0.write_str("<br/>"),
+            _ => self.0.write_char(c),
+        }
+    }
+}
+
+struct Escaped<T>(T);
+
+impl<T: fmt::Display> fmt::Display for Escaped<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(&mut Escaper(f), "{}", &self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Event, Hash};
+
+    #[test]
+    fn snapshot() {
+        let mut g = Graph::default();
+        let mut ev = |arg: &str, deps: &[(Hash, bool)]| {
+            g.ensure_event(Event {
+                cmd: 0,
+                arg: arg.to_string(),
+                deps: deps.iter().copied().collect(),
+            })
+            .1
+        };
+        let a = ev("a", &[]);
+        let b = ev("\"b\"", &[(a, true)]);
+        let c = ev("c", &[(b, false), (a, true)]);
+        g.nstates
+            .insert("main".to_string(), [c].into_iter().collect());
+
+        let opts = ExportOptions::default()
+            .abbrev(true)
+            .edge_styles(true)
+            .state_colours(true);
+        let out = Mermaid::with_options(&g, opts).to_string();
+        let expected = r#"flowchart TD
+  e0["ud_5Jvv<br/>0:c"]
+  e1["wDwgLRu<br/>0:#quot;b#quot;"]
+  e2["y06qQbZ<br/>0:a"]
+  e0 -.-> e1
+  e0 --> e2
+  e1 --> e2
+  s0(["main"])
+  s0 -.- e0
+  style s0 fill:red
+"#;
+        assert_eq!(out, expected);
+
+        // formatting falls back to the whole graph
+        let missing = ExportOptions::default().state("nope");
+        assert!(Mermaid::with_options(&g, missing).render().is_err());
+        let missing = ExportOptions::default().state("nope");
+        let out = Mermaid::with_options(&g, missing).to_string();
+        let nodes = out
+            .lines()
+            .filter(|l| l.starts_with("  e") && l.contains("[\""));
+        assert_eq!(nodes.count(), 3);
+    }
+}
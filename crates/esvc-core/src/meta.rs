@@ -0,0 +1,107 @@
+use crate::{Graph, GraphError, Hash};
+use serde::{Deserialize, Serialize};
+
+/// Metadata of an event, which isn't part of its hash
+/// (and thus might differ between copies of a graph).
+// NOTE: the order of the fields is part of the data format
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct EventMeta {
+    pub author: Option<String>,
+    /// seconds since the unix epoch
+    pub timestamp: Option<u64>,
+    pub message: Option<String>,
+}
+
+impl EventMeta {
+    /// empty metadata with the timestamp set to the current time
+    pub fn now() -> Self {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .ok();
+        Self {
+            timestamp,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// combine two metadata records of the same event.
+    /// missing fields are taken from the other record, conflicting fields
+    /// from the older one (records without timestamp count as newest,
+    /// remaining ties are broken by comparing the records),
+    /// which makes this commutative.
+    pub fn merge(self, other: Self) -> Self {
+        let key = |m: &Self| (m.timestamp.is_none(), m.timestamp);
+        let (first, second) = if (key(&self), &self) <= (key(&other), &other) {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        Self {
+            author: first.author.or(second.author),
+            timestamp: first.timestamp.or(second.timestamp),
+            message: first.message.or(second.message),
+        }
+    }
+}
+
+impl<Arg> Graph<Arg> {
+    /// merge `meta` into the metadata of `evid`
+    pub fn merge_meta(&mut self, evid: Hash, meta: EventMeta) -> Result<(), GraphError> {
+        if !self.events.contains_key(&evid) {
+            return Err(GraphError::DependencyNotFound(evid));
+        }
+        if !meta.is_empty() {
+            let old = self.meta.remove(&evid).unwrap_or_default();
+            self.meta.insert(evid, old.merge(meta));
+        }
+        Ok(())
+    }
+
+    /// merge the metadata of all events of `other` which are present in `self`
+    pub fn merge_meta_from(&mut self, other: &Graph<Arg>) {
+        for (h, m) in &other.meta {
+            // events which aren't present are skipped
+            let _ = self.merge_meta(*h, m.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(author: &str, timestamp: Option<u64>, message: Option<&str>) -> EventMeta {
+        EventMeta {
+            author: Some(author.to_string()),
+            timestamp,
+            message: message.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn merge_rules() {
+        let a = meta("alice", Some(10), None);
+        let b = meta("bob", Some(5), Some("fix typo"));
+        let c = meta("carol", None, Some("other"));
+
+        // the older record wins conflicts, missing fields get filled in
+        assert_eq!(a.clone().merge(b.clone()), b);
+        assert_eq!(
+            a.clone().merge(c.clone()),
+            meta("alice", Some(10), Some("other"))
+        );
+        for (x, y) in [(&a, &b), (&a, &c), (&b, &c)] {
+            assert_eq!(x.clone().merge(y.clone()), y.clone().merge(x.clone()));
+        }
+        assert_eq!(a.clone().merge(EventMeta::default()), a);
+
+        // ties are broken deterministically
+        let a2 = meta("alice2", Some(10), None);
+        assert_eq!(a.clone().merge(a2.clone()), a2.merge(a));
+    }
+}
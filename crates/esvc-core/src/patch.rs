@@ -0,0 +1,252 @@
+// `PatchError::HashMismatch` carries two hashes, which makes it rather large
+#![allow(clippy::result_large_err)]
+
+use crate::{Event, EventMeta, Graph, GraphError, Hash};
+use core::fmt;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// the first word of every text patch
+pub const PATCH_MAGIC: &str = "esvc-event";
+
+#[derive(Debug, thiserror::Error)]
+pub enum PatchError {
+    #[error("invalid patch, line {line}: {msg}")]
+    Syntax { line: usize, msg: String },
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("hash mismatch: patch claims {expected}, but the event hashes to {got}")]
+    HashMismatch { expected: Hash, got: Hash },
+
+    #[error(transparent)]
+    Graph(#[from] GraphError),
+
+    #[error("hash collision @ {0} detected during insertion of patch")]
+    Collision(Hash),
+}
+
+/// A human-readable representation of a single event,
+/// e.g. for code review.
+///
+/// The header contains the hash, `cmd`, dependencies (`dep hard <hash>` or
+/// `dep soft <hash>`) and metadata (as JSON values) of the event, one item
+/// per line. It is followed by an empty line and the argument,
+/// encoded as JSON.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Patch<Arg> {
+    pub evid: Hash,
+    pub event: Event<Arg>,
+    pub meta: EventMeta,
+}
+
+impl<Arg: Serialize> fmt::Display for Patch<Arg> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", PATCH_MAGIC, self.evid)?;
+        writeln!(f, "cmd {}", self.event.cmd)?;
+        for (dep, &is_hard) in &self.event.deps {
+            writeln!(f, "dep {} {}", if is_hard { "hard" } else { "soft" }, dep)?;
+        }
+        if let Some(author) = &self.meta.author {
+            writeln!(
+                f,
+                "author {}",
+                serde_json::to_string(author).map_err(|_| fmt::Error)?
+            )?;
+        }
+        if let Some(timestamp) = self.meta.timestamp {
+            writeln!(f, "timestamp {}", timestamp)?;
+        }
+        if let Some(message) = &self.meta.message {
+            writeln!(
+                f,
+                "message {}",
+                serde_json::to_string(message).map_err(|_| fmt::Error)?
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{}",
+            serde_json::to_string_pretty(&self.event.arg).map_err(|_| fmt::Error)?
+        )
+    }
+}
+
+impl<Arg> Patch<Arg> {
+    /// parse a patch and check that the event matches its hash
+    pub fn parse(s: &str) -> Result<Self, PatchError>
+    where
+        Arg: Serialize + DeserializeOwned,
+    {
+        let (header, body) = s.split_once("\n\n").ok_or(PatchError::Syntax {
+            line: s.lines().count(),
+            msg: "missing empty line after the header".to_string(),
+        })?;
+
+        let mut evid = None;
+        let mut cmd = None;
+        let mut deps = std::collections::BTreeMap::new();
+        let mut meta = EventMeta::default();
+        for (lnum, line) in header.lines().enumerate() {
+            let syntax = |msg: String| PatchError::Syntax {
+                line: lnum + 1,
+                msg,
+            };
+            let hash = |x: &str| x.parse::<Hash>().map_err(|e| syntax(e.to_string()));
+            let (key, val) = line.split_once(' ').unwrap_or((line, ""));
+            match (lnum, key) {
+                (0, PATCH_MAGIC) => evid = Some(hash(val)?),
+                (0, _) => return Err(syntax(format!("expected '{}'", PATCH_MAGIC))),
+                (_, "cmd") => cmd = Some(val.parse().map_err(|e| syntax(format!("{}", e)))?),
+                (_, "dep") => {
+                    let (kind, dep) = val.split_once(' ').unwrap_or((val, ""));
+                    let is_hard = match kind {
+                        "hard" => true,
+                        "soft" => false,
+                        _ => return Err(syntax(format!("invalid dependency kind '{}'", kind))),
+                    };
+                    deps.insert(hash(dep)?, is_hard);
+                }
+                (_, "author") => meta.author = Some(serde_json::from_str(val)?),
+                (_, "timestamp") => {
+                    meta.timestamp = Some(val.parse().map_err(|e| syntax(format!("{}", e)))?)
+                }
+                (_, "message") => meta.message = Some(serde_json::from_str(val)?),
+                _ => return Err(syntax(format!("unknown header item '{}'", key))),
+            }
+        }
+
+        let evid = evid.ok_or(PatchError::Syntax {
+            line: 1,
+            msg: "empty header".to_string(),
+        })?;
+        let event = Event {
+            cmd: cmd.ok_or(PatchError::Syntax {
+                line: header.lines().count(),
+                msg: "missing 'cmd'".to_string(),
+            })?,
+            arg: serde_json::from_str(body)?,
+            deps,
+        };
+        let serval = bincode::serialize(&event).unwrap();
+        let got = evid.algo().calculate(&serval[..]);
+        if got != evid {
+            return Err(PatchError::HashMismatch {
+                expected: evid,
+                got,
+            });
+        }
+        Ok(Self { evid, event, meta })
+    }
+}
+
+impl<Arg: Clone> Graph<Arg> {
+    /// @returns the patch of the event `evid`, including its metadata
+    pub fn patch(&self, evid: &Hash) -> Result<Patch<Arg>, GraphError> {
+        let event = self
+            .events
+            .get(evid)
+            .ok_or(GraphError::DependencyNotFound(*evid))?;
+        Ok(Patch {
+            evid: *evid,
+            event: event.clone(),
+            meta: self.meta.get(evid).cloned().unwrap_or_default(),
+        })
+    }
+}
+
+impl<Arg> Graph<Arg> {
+    /// insert the event of a patch, its dependencies need to be present already
+    pub fn apply_patch(&mut self, patch: Patch<Arg>) -> Result<Hash, PatchError>
+    where
+        Arg: esvc_traits::CommandArg,
+    {
+        if let Some(dep) = patch
+            .event
+            .deps
+            .keys()
+            .find(|dep| !self.events.contains_key(dep))
+        {
+            return Err(GraphError::DependencyNotFound(*dep).into());
+        }
+        let (coll, h) = self.ensure_event(patch.event);
+        if coll.is_some() {
+            return Err(PatchError::Collision(h));
+        } else if h != patch.evid {
+            // the patch was made with a different hash algorithm
+            return Err(PatchError::HashMismatch {
+                expected: patch.evid,
+                got: h,
+            });
+        }
+        self.merge_meta(h, patch.meta)?;
+        Ok(h)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let mut g = Graph::default();
+        let mut ev = |arg: &str, deps: &[(Hash, bool)]| {
+            g.ensure_event(Event {
+                cmd: 1,
+                arg: arg.to_string(),
+                deps: deps.iter().copied().collect(),
+            })
+            .1
+        };
+        let a = ev("a", &[]);
+        let b = ev("b", &[]);
+        let c = ev("line 1\n\"line 2\"", &[(a, true), (b, false)]);
+        g.merge_meta(
+            c,
+            EventMeta {
+                author: Some("alice".to_string()),
+                timestamp: Some(1234),
+                message: Some("fix\nquotes".to_string()),
+            },
+        )
+        .unwrap();
+
+        let text = g.patch(&c).unwrap().to_string();
+        assert_eq!(
+            text,
+            format!(
+                "esvc-event {}\ncmd 1\ndep hard {}\ndep soft {}\nauthor \"alice\"\n\
+                 timestamp 1234\nmessage \"fix\\nquotes\"\n\n\"line 1\\n\\\"line 2\\\"\"\n",
+                c, a, b
+            )
+        );
+
+        let patch = Patch::<String>::parse(&text).unwrap();
+        assert_eq!(patch, g.patch(&c).unwrap());
+
+        let mut other = Graph::default();
+        assert!(matches!(
+            other.apply_patch(patch.clone()),
+            Err(PatchError::Graph(GraphError::DependencyNotFound(_)))
+        ));
+        for h in [a, b] {
+            other.apply_patch(g.patch(&h).unwrap()).unwrap();
+        }
+        assert_eq!(other.apply_patch(patch).unwrap(), c);
+        assert_eq!(other.events, g.events);
+        assert_eq!(other.meta, g.meta);
+
+        // edits of the body are detected
+        let tampered = text.replace("line 2", "line two");
+        assert!(matches!(
+            Patch::<String>::parse(&tampered),
+            Err(PatchError::HashMismatch { expected, .. }) if expected == c
+        ));
+        assert!(matches!(
+            Patch::<String>::parse("esvc-event x\n\n\"\""),
+            Err(PatchError::Syntax { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{Graph, GraphError, Hash};
+use std::collections::BTreeSet;
+
+impl<Arg> Graph<Arg> {
+    /// @returns `evids` and all events they (transitively) depend on,
+    /// which is the closure of a state
+    pub fn ancestors(&self, evids: &BTreeSet<Hash>) -> Result<BTreeSet<Hash>, GraphError> {
+        let mut ret = BTreeSet::new();
+        let mut pending: Vec<Hash> = evids.iter().copied().collect();
+        while let Some(h) = pending.pop() {
+            if ret.contains(&h) {
+                continue;
+            }
+            let ev = self
+                .events
+                .get(&h)
+                .ok_or(GraphError::DependencyNotFound(h))?;
+            ret.insert(h);
+            pending.extend(ev.deps.keys().filter(|&i| !ret.contains(i)).copied());
+        }
+        Ok(ret)
+    }
+
+    /// @returns `evids` and all events which (transitively) depend on them
+    pub fn descendants(&self, evids: &BTreeSet<Hash>) -> BTreeSet<Hash> {
+        let mut ret = BTreeSet::new();
+        let mut pending: Vec<Hash> = evids.iter().copied().collect();
+        while let Some(h) = pending.pop() {
+            if ret.insert(h) {
+                pending.extend(self.dependents(&h).keys().filter(|&i| !ret.contains(i)));
+            }
+        }
+        ret
+    }
+
+    /// @returns the members of `evids` which aren't ancestors of other members
+    pub fn heads(&self, evids: &BTreeSet<Hash>) -> Result<BTreeSet<Hash>, GraphError> {
+        let mut deps = BTreeSet::new();
+        for h in evids {
+            let ev = self
+                .events
+                .get(h)
+                .ok_or(GraphError::DependencyNotFound(*h))?;
+            deps.extend(ev.deps.keys().copied());
+        }
+        let covered = self.ancestors(&deps)?;
+        Ok(evids.difference(&covered).copied().collect())
+    }
+
+    /// @returns the heads of the events which are part of both state closures,
+    /// the equivalent of a merge base
+    pub fn merge_base(
+        &self,
+        a: &BTreeSet<Hash>,
+        b: &BTreeSet<Hash>,
+    ) -> Result<BTreeSet<Hash>, GraphError> {
+        let common = self
+            .ancestors(a)?
+            .intersection(&self.ancestors(b)?)
+            .copied()
+            .collect();
+        self.heads(&common)
+    }
+
+    /// @returns the events which are part of the closure of `a`,
+    /// but not of the closure of `b`
+    pub fn only_in(
+        &self,
+        a: &BTreeSet<Hash>,
+        b: &BTreeSet<Hash>,
+    ) -> Result<BTreeSet<Hash>, GraphError> {
+        let b = self.ancestors(b)?;
+        Ok(self
+            .ancestors(a)?
+            .into_iter()
+            .filter(|h| !b.contains(h))
+            .collect())
+    }
+
+    /// check if `desc` (transitively) depends on `anc`.
+    /// every event counts as its own ancestor.
+    pub fn is_ancestor(&self, anc: &Hash, desc: &Hash) -> Result<bool, GraphError> {
+        let mut seen = BTreeSet::new();
+        let mut pending = vec![*desc];
+        while let Some(h) = pending.pop() {
+            if h == *anc {
+                return Ok(true);
+            }
+            if !seen.insert(h) {
+                continue;
+            }
+            let ev = self
+                .events
+                .get(&h)
+                .ok_or(GraphError::DependencyNotFound(h))?;
+            pending.extend(ev.deps.keys().filter(|&i| !seen.contains(i)));
+        }
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Event;
+
+    fn ev(arg: &str, deps: &[Hash]) -> Event<String> {
+        Event {
+            cmd: 0,
+            arg: arg.to_string(),
+            deps: deps.iter().map(|&h| (h, true)).collect(),
+        }
+    }
+
+    fn set(hs: &[Hash]) -> BTreeSet<Hash> {
+        hs.iter().copied().collect()
+    }
+
+    #[test]
+    fn queries() {
+        //   a
+        //  / \
+        // b   c
+        // |   |\
+        // d   e f
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(ev("a", &[]));
+        let (_, b) = g.ensure_event(ev("b", &[a]));
+        let (_, c) = g.ensure_event(ev("c", &[a]));
+        let (_, d) = g.ensure_event(ev("d", &[b]));
+        let (_, e) = g.ensure_event(ev("e", &[c]));
+        let (_, f) = g.ensure_event(ev("f", &[c]));
+
+        assert_eq!(g.ancestors(&set(&[d, e])).unwrap(), set(&[a, b, c, d, e]));
+        assert_eq!(g.descendants(&set(&[c])), set(&[c, e, f]));
+        assert_eq!(g.heads(&set(&[a, b, c, e])).unwrap(), set(&[b, e]));
+        assert_eq!(g.merge_base(&set(&[d]), &set(&[e, f])).unwrap(), set(&[a]));
+        assert_eq!(g.merge_base(&set(&[e]), &set(&[f])).unwrap(), set(&[c]));
+        assert_eq!(g.only_in(&set(&[d]), &set(&[e])).unwrap(), set(&[b, d]));
+        assert_eq!(g.only_in(&set(&[e]), &set(&[d])).unwrap(), set(&[c, e]));
+
+        assert!(g.is_ancestor(&a, &e).unwrap());
+        assert!(g.is_ancestor(&e, &e).unwrap());
+        assert!(!g.is_ancestor(&b, &e).unwrap());
+        assert!(!g.is_ancestor(&e, &a).unwrap());
+    }
+
+    #[test]
+    fn missing_events() {
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(ev("a", &[]));
+        let (_, missing) = Graph::default().ensure_event(ev("missing", &[]));
+        assert!(matches!(
+            g.ancestors(&set(&[a, missing])),
+            Err(GraphError::DependencyNotFound(h)) if h == missing
+        ));
+        assert!(g.is_ancestor(&missing, &a).is_ok());
+        assert!(g.is_ancestor(&a, &missing).is_err());
+    }
+}
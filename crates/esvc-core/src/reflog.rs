@@ -0,0 +1,196 @@
+use crate::{is_tag, Graph, GraphError, Hash, RefError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+// NOTE: the order of the variants is part of the data format
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ReflogReason {
+    /// a new event was shelved on top of the state
+    Shelve,
+    Merge,
+    Manual,
+    /// restored from the reflog entry with the given sequence number
+    Restore(u64),
+}
+
+/// A single update of a named state.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ReflogEntry {
+    /// strictly increasing across all states of a graph
+    pub seq: u64,
+    pub name: String,
+    /// `None` if the state got created
+    pub old: Option<BTreeSet<Hash>>,
+    /// `None` if the state got deleted
+    pub new: Option<BTreeSet<Hash>>,
+    pub reason: ReflogReason,
+}
+
+impl<Arg> Graph<Arg> {
+    fn log_update(
+        &mut self,
+        name: &str,
+        old: Option<BTreeSet<Hash>>,
+        new: Option<BTreeSet<Hash>>,
+        reason: ReflogReason,
+    ) {
+        if old == new {
+            return;
+        }
+        let seq = self.reflog.last().map(|e| e.seq + 1).unwrap_or(0);
+        self.reflog.push(ReflogEntry {
+            seq,
+            name: name.to_string(),
+            old,
+            new,
+            reason,
+        });
+    }
+
+    /// set a named state and record the update in the reflog
+    ///
+    /// @returns the previous value of the state
+    pub fn set_state(
+        &mut self,
+        name: &str,
+        st: BTreeSet<Hash>,
+        reason: ReflogReason,
+    ) -> Option<BTreeSet<Hash>> {
+        let old = self.nstates.insert(name.to_string(), st.clone());
+        self.log_update(name, old.clone(), Some(st), reason);
+        old
+    }
+
+    /// remove a named state and record the update in the reflog
+    pub fn remove_state(&mut self, name: &str, reason: ReflogReason) -> Option<BTreeSet<Hash>> {
+        let old = self.nstates.remove(name);
+        if old.is_some() {
+            self.log_update(name, old.clone(), None, reason);
+        }
+        old
+    }
+
+    /// iterate over the reflog entries of a single state, oldest first
+    pub fn reflog_of<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a ReflogEntry> + 'a {
+        self.reflog.iter().filter(move |e| e.name == name)
+    }
+
+    /// drop all but the newest `keep_last` reflog entries, so that they
+    /// don't keep their states alive during [`Graph::gc`] anymore.
+    /// the newest entry is always kept, because the sequence numbers
+    /// of new entries continue after it.
+    ///
+    /// @returns the number of dropped entries
+    pub fn expire_reflog(&mut self, keep_last: usize) -> usize {
+        let n = self.reflog.len().saturating_sub(keep_last.max(1));
+        self.reflog.drain(..n);
+        n
+    }
+
+    /// reset the state of the reflog entry `seq` to the value it got
+    /// by that update (or delete it if that update deleted it).
+    /// to undo an update, restore the entry before it.
+    pub fn restore_reflog(&mut self, seq: u64) -> Result<(), RefError> {
+        let entry = self
+            .reflog
+            .iter()
+            .find(|e| e.seq == seq)
+            .ok_or(RefError::ReflogEntryNotFound(seq))?;
+        let (name, new) = (entry.name.clone(), entry.new.clone());
+        if is_tag(&name)
+            && self.nstates.contains_key(&name)
+            && self.nstates.get(&name) != new.as_ref()
+        {
+            return Err(RefError::Immutable(name));
+        }
+        let reason = ReflogReason::Restore(seq);
+        match new {
+            Some(st) => {
+                if let Some(&h) = st.iter().find(|h| !self.events.contains_key(h)) {
+                    return Err(GraphError::DependencyNotFound(h).into());
+                }
+                self.set_state(&name, st, reason);
+            }
+            None => {
+                self.remove_state(&name, reason);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Event;
+
+    #[test]
+    fn restore() {
+        let mut g = Graph::default();
+        let mut hs = Vec::new();
+        for i in ["a", "b", "c"] {
+            let (_, h) = g.ensure_event(Event {
+                cmd: 0,
+                arg: i.to_string(),
+                deps: Default::default(),
+            });
+            hs.push(h);
+        }
+        let st = |i: usize| -> BTreeSet<Hash> { hs[..i].iter().copied().collect() };
+
+        g.set_state("", st(1), ReflogReason::Shelve);
+        g.set_state("", st(1), ReflogReason::Shelve);
+        g.set_state("x", st(1), ReflogReason::Manual);
+        g.set_state("", st(3), ReflogReason::Merge);
+        g.remove_state("x", ReflogReason::Manual);
+        assert_eq!(g.reflog.len(), 4);
+        assert_eq!(g.reflog_of("").count(), 2);
+        assert_eq!(g.reflog[3].new, None);
+
+        // undo the merge
+        g.restore_reflog(0).unwrap();
+        assert_eq!(g.nstates[""], st(1));
+        let last = g.reflog.last().unwrap();
+        assert_eq!(last.reason, ReflogReason::Restore(0));
+        assert_eq!(last.old, Some(st(3)));
+        assert_eq!(last.seq, 4);
+
+        g.restore_reflog(3).unwrap();
+        assert!(!g.nstates.contains_key("x"));
+        g.restore_reflog(1).unwrap();
+        assert_eq!(g.nstates["x"], st(1));
+        assert_eq!(
+            g.restore_reflog(100),
+            Err(RefError::ReflogEntryNotFound(100))
+        );
+    }
+
+    #[test]
+    fn expire() {
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(Event {
+            cmd: 0,
+            arg: "a".to_string(),
+            deps: Default::default(),
+        });
+        let (_, b) = g.ensure_event(Event {
+            cmd: 0,
+            arg: "b".to_string(),
+            deps: Default::default(),
+        });
+        g.set_state("", [a].into_iter().collect(), ReflogReason::Shelve);
+        g.set_state("", [b].into_iter().collect(), ReflogReason::Manual);
+        g.set_state("x", [b].into_iter().collect(), ReflogReason::Manual);
+
+        // `a` is only referenced by the reflog
+        assert!(g.gc(false).unwrap().is_empty());
+        assert_eq!(g.expire_reflog(0), 2);
+        assert_eq!(g.reflog.len(), 1);
+        assert_eq!(g.gc(false).unwrap(), [a].into_iter().collect());
+
+        // sequence numbers aren't reused
+        g.remove_state("x", ReflogReason::Manual);
+        assert_eq!(g.reflog.last().unwrap().seq, 3);
+        assert_eq!(g.expire_reflog(5), 0);
+    }
+}
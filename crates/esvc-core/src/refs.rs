@@ -0,0 +1,252 @@
+use crate::{Graph, GraphError, Hash, ReflogReason};
+use std::collections::BTreeSet;
+
+/// refs in this namespace are immutable once created
+pub const TAG_PREFIX: &str = "tag/";
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum RefError {
+    #[error(transparent)]
+    Graph(#[from] GraphError),
+
+    #[error("invalid ref name '{0}'")]
+    InvalidName(String),
+
+    #[error("ref '{0}' already exists")]
+    AlreadyExists(String),
+
+    #[error("ref '{0}' not found")]
+    NotFound(String),
+
+    #[error("ref '{0}' is a tag, and thus can't be moved or deleted")]
+    Immutable(String),
+
+    /// the ref was modified since it was read
+    #[error("ref '{name}' was changed concurrently")]
+    Conflict {
+        name: String,
+        actual: Option<BTreeSet<Hash>>,
+    },
+
+    #[error("reflog entry {0} not found")]
+    ReflogEntryNotFound(u64),
+}
+
+/// check if `name` is usable as ref name. names consist of `/`-separated
+/// non-empty components without whitespace or control characters,
+/// `.` and `..` aren't allowed as components.
+/// the empty name is the default state.
+pub fn check_ref_name(name: &str) -> Result<(), RefError> {
+    if name.is_empty() {
+        return Ok(());
+    }
+    let valid = name.split('/').all(|comp| {
+        !comp.is_empty()
+            && comp != "."
+            && comp != ".."
+            && !comp.chars().any(|c| c.is_whitespace() || c.is_control())
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(RefError::InvalidName(name.to_string()))
+    }
+}
+
+pub fn is_tag(name: &str) -> bool {
+    name.starts_with(TAG_PREFIX)
+}
+
+impl<Arg> Graph<Arg> {
+    fn check_members(&self, st: &BTreeSet<Hash>) -> Result<(), RefError> {
+        match st.iter().find(|h| !self.events.contains_key(h)) {
+            Some(&h) => Err(GraphError::DependencyNotFound(h).into()),
+            None => Ok(()),
+        }
+    }
+
+    /// iterate over all refs in the namespace `ns` (e.g. `branch`),
+    /// including refs in nested namespaces
+    pub fn refs_in<'a>(
+        &'a self,
+        ns: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a BTreeSet<Hash>)> + 'a {
+        self.nstates
+            .range::<str, _>((core::ops::Bound::Excluded(ns), core::ops::Bound::Unbounded))
+            .take_while(move |(name, _)| name.starts_with(ns))
+            .filter(move |(name, _)| name.as_bytes().get(ns.len()) == Some(&b'/'))
+            .map(|(name, st)| (&name[..], st))
+    }
+
+    pub fn create_ref(&mut self, name: &str, st: BTreeSet<Hash>) -> Result<(), RefError> {
+        self.update_ref(name, None, st)
+    }
+
+    /// create the ref `to` as copy of `from`
+    pub fn fork_ref(&mut self, from: &str, to: &str) -> Result<(), RefError> {
+        let st = self
+            .nstates
+            .get(from)
+            .ok_or_else(|| RefError::NotFound(from.to_string()))?
+            .clone();
+        self.create_ref(to, st)
+    }
+
+    /// rename a ref, tags and the default state can't be renamed
+    pub fn rename_ref(&mut self, from: &str, to: &str) -> Result<(), RefError> {
+        check_ref_name(to)?;
+        if let Some(name) = [from, to].into_iter().find(|name| name.is_empty()) {
+            return Err(RefError::InvalidName(name.to_string()));
+        } else if !self.nstates.contains_key(from) {
+            return Err(RefError::NotFound(from.to_string()));
+        } else if is_tag(from) {
+            return Err(RefError::Immutable(from.to_string()));
+        } else if self.nstates.contains_key(to) {
+            return Err(RefError::AlreadyExists(to.to_string()));
+        }
+        let st = self.remove_state(from, ReflogReason::Manual).unwrap();
+        self.set_state(to, st, ReflogReason::Manual);
+        Ok(())
+    }
+
+    /// delete a ref, tags can only be deleted if `force` is set,
+    /// because recreating them would move them.
+    ///
+    /// @returns the members of the deleted ref
+    pub fn delete_ref(&mut self, name: &str, force: bool) -> Result<BTreeSet<Hash>, RefError> {
+        if !self.nstates.contains_key(name) {
+            return Err(RefError::NotFound(name.to_string()));
+        } else if is_tag(name) && !force {
+            return Err(RefError::Immutable(name.to_string()));
+        }
+        Ok(self.remove_state(name, ReflogReason::Manual).unwrap())
+    }
+
+    /// compare-and-swap: set the ref `name` to `new`, but only if its
+    /// current value is `expected` (`None` means that it doesn't exist yet).
+    /// all modifications via this API are recorded in the reflog.
+    pub fn update_ref(
+        &mut self,
+        name: &str,
+        expected: Option<&BTreeSet<Hash>>,
+        new: BTreeSet<Hash>,
+    ) -> Result<(), RefError> {
+        check_ref_name(name)?;
+        let actual = self.nstates.get(name);
+        if actual != expected {
+            return Err(match (expected, actual) {
+                (None, Some(_)) => RefError::AlreadyExists(name.to_string()),
+                _ => RefError::Conflict {
+                    name: name.to_string(),
+                    actual: actual.cloned(),
+                },
+            });
+        }
+        if actual.is_some() && is_tag(name) {
+            return Err(RefError::Immutable(name.to_string()));
+        }
+        self.check_members(&new)?;
+        self.set_state(name, new, ReflogReason::Manual);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Event;
+
+    fn mkgraph() -> (Graph<String>, Hash, Hash) {
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(Event {
+            cmd: 0,
+            arg: "a".to_string(),
+            deps: Default::default(),
+        });
+        let (_, b) = g.ensure_event(Event {
+            cmd: 0,
+            arg: "b".to_string(),
+            deps: [(a, true)].into_iter().collect(),
+        });
+        (g, a, b)
+    }
+
+    #[test]
+    fn names() {
+        for i in ["", "main", "branch/foo", "tag/v1.0", "a/b/c"] {
+            assert_eq!(check_ref_name(i), Ok(()), "{}", i);
+        }
+        for i in ["/", "a/", "/a", "a//b", "a/../b", ".", "a b", "a\n"] {
+            assert!(check_ref_name(i).is_err(), "{:?}", i);
+        }
+    }
+
+    #[test]
+    fn lifecycle() {
+        let (mut g, a, b) = mkgraph();
+        let sa: BTreeSet<_> = [a].into_iter().collect();
+        let sb: BTreeSet<_> = [b].into_iter().collect();
+
+        g.create_ref("branch/foo", sa.clone()).unwrap();
+        assert_eq!(
+            g.create_ref("branch/foo", sa.clone()),
+            Err(RefError::AlreadyExists("branch/foo".to_string()))
+        );
+        g.fork_ref("branch/foo", "branch/foo/bar").unwrap();
+        g.fork_ref("branch/foo", "tag/v1").unwrap();
+        g.create_ref("branchless", sa.clone()).unwrap();
+
+        // compare-and-swap
+        g.update_ref("branch/foo", Some(&sa), sb.clone()).unwrap();
+        assert_eq!(
+            g.update_ref("branch/foo", Some(&sa), sa.clone()),
+            Err(RefError::Conflict {
+                name: "branch/foo".to_string(),
+                actual: Some(sb.clone())
+            })
+        );
+
+        // tags are immutable
+        assert_eq!(
+            g.update_ref("tag/v1", Some(&sa), sb.clone()),
+            Err(RefError::Immutable("tag/v1".to_string()))
+        );
+        assert_eq!(
+            g.rename_ref("tag/v1", "tag/v2"),
+            Err(RefError::Immutable("tag/v1".to_string()))
+        );
+
+        g.rename_ref("branch/foo/bar", "branch/baz").unwrap();
+        g.create_ref("", sa.clone()).unwrap();
+        for (from, to) in [("", "branch/default"), ("branchless", "")] {
+            assert_eq!(
+                g.rename_ref(from, to),
+                Err(RefError::InvalidName(String::new()))
+            );
+        }
+        let names: Vec<_> = g.refs_in("branch").map(|(name, _)| name).collect();
+        assert_eq!(names, ["branch/baz", "branch/foo"]);
+
+        assert_eq!(
+            g.delete_ref("tag/v1", false),
+            Err(RefError::Immutable("tag/v1".to_string()))
+        );
+        assert_eq!(g.delete_ref("tag/v1", true), Ok(sa));
+        // create, 2x fork, create, update, rename (2 entries), create, delete
+        assert_eq!(g.reflog.len(), 9);
+        assert_eq!(
+            g.delete_ref("tag/v1", true),
+            Err(RefError::NotFound("tag/v1".to_string()))
+        );
+
+        let (_, missing) = Graph::<String>::default().ensure_event(Event {
+            cmd: 1,
+            arg: String::new(),
+            deps: Default::default(),
+        });
+        assert_eq!(
+            g.create_ref("x", [missing].into_iter().collect()),
+            Err(GraphError::DependencyNotFound(missing).into())
+        );
+    }
+}
@@ -0,0 +1,613 @@
+use crate::{Graph, GraphError, Hash, PrefixError};
+use std::collections::BTreeSet;
+
+/// Selects which dependency edges are followed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeFilter {
+    Any,
+    Hard,
+    Soft,
+}
+
+impl EdgeFilter {
+    fn matches(self, is_hard: bool) -> bool {
+        match self {
+            EdgeFilter::Any => true,
+            EdgeFilter::Hard => is_hard,
+            EdgeFilter::Soft => !is_hard,
+        }
+    }
+}
+
+/// A parsed expression which selects a set of events.
+///
+/// Syntax:
+/// - `"name"`: the members of a named state (`""` is the default state)
+/// - `abc123`, `blake3:abc123`: an unambiguous hash prefix
+/// - `all()`, `cmd(n)`: all events, all events with command ID `n`
+/// - `ancestors(x)`, `descendants(x)`: `x` and everything reachable from it
+/// - `deps(x)`, `dependents(x)`: direct neighbors of `x`
+/// - `heads(x)`: members of `x` which aren't ancestors of other members
+/// - `x | y`, `x & y`, `x ~ y`, `~x`: union, intersection, difference, complement
+///
+/// The functions which follow dependency edges accept `hard()` or `soft()`
+/// as optional second argument, e.g. `ancestors("", hard())`.
+/// `&` and `~` bind tighter than `|`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Revset {
+    All,
+    State(String),
+    Prefix(String),
+    Cmd(u32),
+    Ancestors(Box<Revset>, EdgeFilter),
+    Descendants(Box<Revset>, EdgeFilter),
+    Deps(Box<Revset>, EdgeFilter),
+    Dependents(Box<Revset>, EdgeFilter),
+    Heads(Box<Revset>),
+    Union(Box<Revset>, Box<Revset>),
+    Intersection(Box<Revset>, Box<Revset>),
+    Difference(Box<Revset>, Box<Revset>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum RevsetParseErrorKind {
+    #[error("unexpected end of expression")]
+    UnexpectedEnd,
+
+    #[error("unexpected character '{0}'")]
+    UnexpectedChar(char),
+
+    #[error("unterminated string")]
+    UnterminatedString,
+
+    #[error("expected {expected}, found {found}")]
+    Expected {
+        expected: &'static str,
+        found: String,
+    },
+
+    #[error("unknown function '{0}'")]
+    UnknownFunction(String),
+
+    #[error("function '{name}' expects {expected} argument(s), got {got}")]
+    ArgCount {
+        name: String,
+        expected: &'static str,
+        got: usize,
+    },
+
+    #[error("invalid command ID '{0}'")]
+    InvalidCmd(String),
+
+    #[error("'{0}()' is only allowed as edge filter argument")]
+    MisplacedFilter(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("{kind} at position {pos}")]
+pub struct RevsetParseError {
+    /// byte offset into the expression
+    pub pos: usize,
+    pub kind: RevsetParseErrorKind,
+}
+
+impl RevsetParseError {
+    /// render the error with the expression and a marker below the position
+    pub fn render(&self, input: &str) -> String {
+        let col = input
+            .get(..self.pos)
+            .map(|i| i.chars().count())
+            .unwrap_or(0);
+        format!("{}\n{}^ {}", input, " ".repeat(col), self.kind)
+    }
+}
+
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum RevsetError {
+    #[error(transparent)]
+    Parse(#[from] RevsetParseError),
+
+    #[error(transparent)]
+    Prefix(#[from] PrefixError),
+
+    #[error(transparent)]
+    Graph(#[from] GraphError),
+
+    #[error("unknown state \"{0}\"")]
+    StateNotFound(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    Comma,
+    Pipe,
+    Amp,
+    Tilde,
+    Str(String),
+    Word(String),
+    End,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::LParen => "'('".to_string(),
+            Token::RParen => "')'".to_string(),
+            Token::Comma => "','".to_string(),
+            Token::Pipe => "'|'".to_string(),
+            Token::Amp => "'&'".to_string(),
+            Token::Tilde => "'~'".to_string(),
+            Token::Str(s) => format!("{:?}", s),
+            Token::Word(w) => format!("'{}'", w),
+            Token::End => "end of expression".to_string(),
+        }
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':')
+}
+
+fn tokenize(s: &str) -> Result<Vec<(usize, Token)>, RevsetParseError> {
+    let mut ret = Vec::new();
+    let mut it = s.char_indices().peekable();
+    while let Some((pos, c)) = it.next() {
+        let tok = match c {
+            _ if c.is_whitespace() => continue,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            ',' => Token::Comma,
+            '|' => Token::Pipe,
+            '&' => Token::Amp,
+            '~' => Token::Tilde,
+            '"' => {
+                let mut x = String::new();
+                loop {
+                    match it.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) if it.peek().is_some() => x.push(it.next().unwrap().1),
+                        Some((_, c)) => x.push(c),
+                        None => {
+                            return Err(RevsetParseError {
+                                pos,
+                                kind: RevsetParseErrorKind::UnterminatedString,
+                            })
+                        }
+                    }
+                }
+                Token::Str(x)
+            }
+            _ if is_word_char(c) => {
+                let mut x = c.to_string();
+                while let Some(&(_, c)) = it.peek() {
+                    if !is_word_char(c) {
+                        break;
+                    }
+                    x.push(c);
+                    it.next();
+                }
+                Token::Word(x)
+            }
+            _ => {
+                return Err(RevsetParseError {
+                    pos,
+                    kind: RevsetParseErrorKind::UnexpectedChar(c),
+                })
+            }
+        };
+        ret.push((pos, tok));
+    }
+    ret.push((s.len(), Token::End));
+    Ok(ret)
+}
+
+/// a function argument, edge filters are only valid in some positions
+enum FnArg {
+    Set(Revset),
+    Filter(usize, &'static str, EdgeFilter),
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    idx: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.idx].1
+    }
+
+    fn pos(&self) -> usize {
+        self.tokens[self.idx].0
+    }
+
+    fn next(&mut self) -> (usize, Token) {
+        let ret = self.tokens[self.idx].clone();
+        if ret.1 != Token::End {
+            self.idx += 1;
+        }
+        ret
+    }
+
+    fn unexpected(&self, expected: &'static str) -> RevsetParseError {
+        let kind = match self.peek() {
+            Token::End => RevsetParseErrorKind::UnexpectedEnd,
+            t => RevsetParseErrorKind::Expected {
+                expected,
+                found: t.describe(),
+            },
+        };
+        RevsetParseError {
+            pos: self.pos(),
+            kind,
+        }
+    }
+
+    fn expect(&mut self, tok: Token, expected: &'static str) -> Result<(), RevsetParseError> {
+        if *self.peek() == tok {
+            self.next();
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn union(&mut self) -> Result<Revset, RevsetParseError> {
+        let mut lhs = self.intersection()?;
+        while *self.peek() == Token::Pipe {
+            self.next();
+            lhs = Revset::Union(Box::new(lhs), Box::new(self.intersection()?));
+        }
+        Ok(lhs)
+    }
+
+    fn intersection(&mut self) -> Result<Revset, RevsetParseError> {
+        let mut lhs = self.primary()?;
+        loop {
+            let f = match self.peek() {
+                Token::Amp => Revset::Intersection,
+                Token::Tilde => Revset::Difference,
+                _ => break Ok(lhs),
+            };
+            self.next();
+            lhs = f(Box::new(lhs), Box::new(self.primary()?));
+        }
+    }
+
+    fn primary(&mut self) -> Result<Revset, RevsetParseError> {
+        match self.arg()? {
+            FnArg::Set(x) => Ok(x),
+            FnArg::Filter(pos, name, _) => Err(RevsetParseError {
+                pos,
+                kind: RevsetParseErrorKind::MisplacedFilter(name.to_string()),
+            }),
+        }
+    }
+
+    fn arg(&mut self) -> Result<FnArg, RevsetParseError> {
+        let (pos, tok) = match self.peek() {
+            Token::LParen | Token::Tilde | Token::Str(_) | Token::Word(_) => self.next(),
+            _ => return Err(self.unexpected("an expression")),
+        };
+        Ok(FnArg::Set(match tok {
+            Token::LParen => {
+                let x = self.union()?;
+                self.expect(Token::RParen, "')'")?;
+                x
+            }
+            Token::Tilde => Revset::Difference(Box::new(Revset::All), Box::new(self.primary()?)),
+            Token::Str(name) => Revset::State(name),
+            Token::Word(w) if *self.peek() == Token::LParen => return self.call(pos, w),
+            Token::Word(w) => Revset::Prefix(w),
+            _ => unreachable!(),
+        }))
+    }
+
+    fn call(&mut self, pos: usize, name: String) -> Result<FnArg, RevsetParseError> {
+        self.next();
+
+        if name == "cmd" {
+            // handled separately, because the command ID looks like a hash prefix
+            let w = match self.peek() {
+                Token::Word(w) => w.clone(),
+                _ => return Err(self.unexpected("a command ID")),
+            };
+            self.next();
+            self.expect(Token::RParen, "')'")?;
+            let cmd = w.parse().map_err(|_| RevsetParseError {
+                pos,
+                kind: RevsetParseErrorKind::InvalidCmd(w),
+            })?;
+            return Ok(FnArg::Set(Revset::Cmd(cmd)));
+        }
+
+        let mut args = Vec::new();
+        if *self.peek() != Token::RParen {
+            loop {
+                args.push(self.arg()?);
+                if *self.peek() != Token::Comma {
+                    break;
+                }
+                self.next();
+            }
+        }
+        self.expect(Token::RParen, "')' or ','")?;
+
+        let argcnt_err = |expected, got| RevsetParseError {
+            pos,
+            kind: RevsetParseErrorKind::ArgCount {
+                name: name.clone(),
+                expected,
+                got,
+            },
+        };
+        let fetch_set = |arg| match arg {
+            FnArg::Set(x) => Ok(Box::new(x)),
+            FnArg::Filter(pos, name, _) => Err(RevsetParseError {
+                pos,
+                kind: RevsetParseErrorKind::MisplacedFilter(name.to_string()),
+            }),
+        };
+
+        Ok(match &name[..] {
+            "all" | "hard" | "soft" if !args.is_empty() => return Err(argcnt_err("0", args.len())),
+            "all" => FnArg::Set(Revset::All),
+            "hard" => FnArg::Filter(pos, "hard", EdgeFilter::Hard),
+            "soft" => FnArg::Filter(pos, "soft", EdgeFilter::Soft),
+            "heads" => {
+                if args.len() != 1 {
+                    return Err(argcnt_err("1", args.len()));
+                }
+                FnArg::Set(Revset::Heads(fetch_set(args.pop().unwrap())?))
+            }
+            "ancestors" | "descendants" | "deps" | "dependents" => {
+                let filter = match args.len() {
+                    1 => EdgeFilter::Any,
+                    2 => match args.pop().unwrap() {
+                        FnArg::Filter(_, _, f) => f,
+                        FnArg::Set(_) => {
+                            return Err(RevsetParseError {
+                                pos,
+                                kind: RevsetParseErrorKind::Expected {
+                                    expected: "'hard()' or 'soft()' as second argument",
+                                    found: "an expression".to_string(),
+                                },
+                            })
+                        }
+                    },
+                    n => return Err(argcnt_err("1 or 2", n)),
+                };
+                let x = fetch_set(args.pop().unwrap())?;
+                FnArg::Set(match &name[..] {
+                    "ancestors" => Revset::Ancestors(x, filter),
+                    "descendants" => Revset::Descendants(x, filter),
+                    "deps" => Revset::Deps(x, filter),
+                    _ => Revset::Dependents(x, filter),
+                })
+            }
+            _ => {
+                return Err(RevsetParseError {
+                    pos,
+                    kind: RevsetParseErrorKind::UnknownFunction(name),
+                })
+            }
+        })
+    }
+}
+
+impl core::str::FromStr for Revset {
+    type Err = RevsetParseError;
+
+    fn from_str(s: &str) -> Result<Revset, RevsetParseError> {
+        let mut p = Parser {
+            tokens: tokenize(s)?,
+            idx: 0,
+        };
+        let ret = p.union()?;
+        if *p.peek() != Token::End {
+            return Err(p.unexpected("an operator or end of expression"));
+        }
+        Ok(ret)
+    }
+}
+
+impl<Arg> Graph<Arg> {
+    /// parse and evaluate a [`Revset`] expression
+    pub fn select(&self, expr: &str) -> Result<BTreeSet<Hash>, RevsetError> {
+        self.eval_revset(&expr.parse()?)
+    }
+
+    pub fn eval_revset(&self, rs: &Revset) -> Result<BTreeSet<Hash>, RevsetError> {
+        Ok(match rs {
+            Revset::All => self.events.keys().copied().collect(),
+            Revset::State(name) => self
+                .nstates
+                .get(name)
+                .cloned()
+                .ok_or_else(|| RevsetError::StateNotFound(name.clone()))?,
+            Revset::Prefix(pfx) => [self.resolve_prefix(pfx)?].into_iter().collect(),
+            Revset::Cmd(cmd) => self
+                .events
+                .iter()
+                .filter(|(_, ev)| ev.cmd == *cmd)
+                .map(|(&h, _)| h)
+                .collect(),
+            Revset::Ancestors(x, EdgeFilter::Any) => self.ancestors(&self.eval_revset(x)?)?,
+            Revset::Ancestors(x, filter) => {
+                self.walk(self.eval_revset(x)?, |h| self.deps_filtered(h, *filter))?
+            }
+            Revset::Descendants(x, filter) => self.walk(self.eval_revset(x)?, |h| {
+                Ok(self.dependents_filtered(h, *filter))
+            })?,
+            Revset::Deps(x, filter) => {
+                let mut ret = BTreeSet::new();
+                for h in self.eval_revset(x)? {
+                    ret.extend(self.deps_filtered(&h, *filter)?);
+                }
+                ret
+            }
+            Revset::Dependents(x, filter) => self
+                .eval_revset(x)?
+                .iter()
+                .flat_map(|h| self.dependents_filtered(h, *filter))
+                .collect(),
+            Revset::Heads(x) => self.heads(&self.eval_revset(x)?)?,
+            Revset::Union(a, b) => {
+                let mut ret = self.eval_revset(a)?;
+                ret.extend(self.eval_revset(b)?);
+                ret
+            }
+            Revset::Intersection(a, b) => {
+                let b = self.eval_revset(b)?;
+                let mut ret = self.eval_revset(a)?;
+                ret.retain(|h| b.contains(h));
+                ret
+            }
+            Revset::Difference(a, b) => {
+                let b = self.eval_revset(b)?;
+                let mut ret = self.eval_revset(a)?;
+                ret.retain(|h| !b.contains(h));
+                ret
+            }
+        })
+    }
+
+    fn deps_filtered(&self, h: &Hash, filter: EdgeFilter) -> Result<Vec<Hash>, GraphError> {
+        Ok(self
+            .events
+            .get(h)
+            .ok_or(GraphError::DependencyNotFound(*h))?
+            .deps
+            .iter()
+            .filter(|&(_, &is_hard)| filter.matches(is_hard))
+            .map(|(&dep, _)| dep)
+            .collect())
+    }
+
+    fn dependents_filtered(&self, h: &Hash, filter: EdgeFilter) -> Vec<Hash> {
+        self.dependents(h)
+            .iter()
+            .filter(|&(_, &is_hard)| filter.matches(is_hard))
+            .map(|(&dep, _)| dep)
+            .collect()
+    }
+
+    /// @returns `start` and everything reachable from it via `next`
+    fn walk(
+        &self,
+        start: BTreeSet<Hash>,
+        next: impl Fn(&Hash) -> Result<Vec<Hash>, GraphError>,
+    ) -> Result<BTreeSet<Hash>, GraphError> {
+        let mut ret = BTreeSet::new();
+        let mut pending: Vec<_> = start.into_iter().collect();
+        while let Some(h) = pending.pop() {
+            if ret.insert(h) {
+                pending.extend(next(&h)?.into_iter().filter(|i| !ret.contains(i)));
+            }
+        }
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Event;
+
+    fn ev(cmd: u32, arg: &str, deps: &[(Hash, bool)]) -> Event<String> {
+        Event {
+            cmd,
+            arg: arg.to_string(),
+            deps: deps.iter().copied().collect(),
+        }
+    }
+
+    fn set(hs: &[Hash]) -> BTreeSet<Hash> {
+        hs.iter().copied().collect()
+    }
+
+    #[test]
+    fn parse() {
+        use Revset::*;
+        let st = |s: &str| Box::new(State(s.to_string()));
+        assert_eq!(
+            "\"a\" | \"b\" & ~\"c\"".parse(),
+            Ok(Union(
+                st("a"),
+                Box::new(Intersection(
+                    st("b"),
+                    Box::new(Difference(Box::new(All), st("c")))
+                ))
+            ))
+        );
+        assert_eq!(
+            "heads(ancestors(sha256:Ab-_, hard())) ~ cmd(3)".parse(),
+            Ok(Difference(
+                Box::new(Heads(Box::new(Ancestors(
+                    Box::new(Prefix("sha256:Ab-_".to_string())),
+                    EdgeFilter::Hard
+                )))),
+                Box::new(Cmd(3))
+            ))
+        );
+        assert_eq!("(\"\\\"\")".parse(), Ok(State("\"".to_string())));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |s: &str| s.parse::<Revset>().unwrap_err();
+        use RevsetParseErrorKind as K;
+        assert_eq!(err("all() |").kind, K::UnexpectedEnd);
+        assert_eq!(err("all() |").pos, 7);
+        assert_eq!(err("foo(x)").kind, K::UnknownFunction("foo".to_string()));
+        assert_eq!(err("\"x").kind, K::UnterminatedString);
+        assert_eq!(err("x $ y").kind, K::UnexpectedChar('$'));
+        assert_eq!(err("x $ y").pos, 2);
+        assert_eq!(err("hard()").kind, K::MisplacedFilter("hard".to_string()));
+        assert_eq!(err("deps(x, y)").pos, 0);
+        assert_eq!(err("cmd(x)").kind, K::InvalidCmd("x".to_string()));
+        assert!(matches!(err("heads()").kind, K::ArgCount { got: 0, .. }));
+        assert!(matches!(err("x y").kind, K::Expected { .. }));
+        assert_eq!(
+            err("all( | x").render("all( | x"),
+            "all( | x\n     ^ expected an expression, found '|'"
+        );
+    }
+
+    #[test]
+    fn eval() {
+        // a <-hard- b <-soft- c <-hard- d, e (cmd 1) <-hard- d
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(ev(0, "a", &[]));
+        let (_, b) = g.ensure_event(ev(0, "b", &[(a, true)]));
+        let (_, c) = g.ensure_event(ev(0, "c", &[(b, false)]));
+        let (_, e) = g.ensure_event(ev(1, "e", &[]));
+        let (_, d) = g.ensure_event(ev(0, "d", &[(c, true), (e, true)]));
+        g.nstates.insert(String::new(), set(&[d]));
+        g.nstates.insert("x".to_string(), set(&[b]));
+
+        let sel = |s: &str| g.select(s).unwrap();
+        assert_eq!(sel("\"\""), set(&[d]));
+        assert_eq!(sel("ancestors(\"\")"), set(&[a, b, c, d, e]));
+        assert_eq!(sel("ancestors(\"\", hard())"), set(&[c, d, e]));
+        assert_eq!(sel("ancestors(\"\") ~ ancestors(\"x\")"), set(&[c, d, e]));
+        assert_eq!(sel("deps(ancestors(\"\"), soft())"), set(&[b]));
+        assert_eq!(sel("dependents(\"x\")"), set(&[c]));
+        assert_eq!(sel("descendants(\"x\", hard())"), set(&[b]));
+        assert_eq!(sel("cmd(1) | heads(all())"), set(&[d, e]));
+        assert_eq!(sel("all() & ~ancestors(\"\")"), BTreeSet::new());
+        assert_eq!(sel(&g.abbrev(&a).to_string()), set(&[a]));
+
+        assert!(matches!(
+            g.select("\"nope\""),
+            Err(RevsetError::StateNotFound(_))
+        ));
+        assert!(matches!(g.select("!"), Err(RevsetError::Parse(_))));
+        assert!(matches!(
+            g.select("AAAAAAAAAAAAA"),
+            Err(RevsetError::Prefix(_))
+        ));
+    }
+}
@@ -0,0 +1,335 @@
+use crate::{hash::HASH_B64_CFG, Graph, Hash};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// An ed25519 public key, which identifies the signer.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct SignerId(pub [u8; 32]);
+
+impl fmt::Display for SignerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&base64::encode_config(self.0, HASH_B64_CFG))
+    }
+}
+
+impl fmt::Debug for SignerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <SignerId as fmt::Display>::fmt(self, f)
+    }
+}
+
+impl core::str::FromStr for SignerId {
+    type Err = base64::DecodeError;
+
+    fn from_str(s: &str) -> Result<SignerId, base64::DecodeError> {
+        let buf = base64::decode_config(s, HASH_B64_CFG)?;
+        buf.try_into()
+            .map(SignerId)
+            .map_err(|_| base64::DecodeError::InvalidLength)
+    }
+}
+
+/// A raw ed25519 signature.
+#[serde_with::serde_as]
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Signature(#[serde_as(as = "serde_with::Bytes")] pub [u8; 64]);
+
+impl fmt::Debug for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&base64::encode_config(self.0, HASH_B64_CFG))
+    }
+}
+
+/// the signatures of a single subject (an event or a state)
+pub type Signatures = BTreeMap<SignerId, Signature>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// signed by a trusted key
+    Good(SignerId),
+    Unsigned,
+    /// only signed by keys which aren't trusted
+    Untrusted(Vec<SignerId>),
+    /// these signatures don't match, which indicates tampering
+    Bad(Vec<SignerId>),
+}
+
+/// The result of [`Graph::verify_signatures`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureReport {
+    /// the status of the signatures of the state itself
+    pub state: SignatureStatus,
+    /// the events in the closure of the state which aren't signed
+    /// by a trusted key
+    pub events: BTreeMap<Hash, SignatureStatus>,
+}
+
+impl SignatureReport {
+    pub fn is_ok(&self) -> bool {
+        matches!(self.state, SignatureStatus::Good(_)) && self.events.is_empty()
+    }
+}
+
+impl<Arg> Graph<Arg> {
+    /// the subject under which signatures of the state `name` are stored,
+    /// it changes whenever the state changes.
+    pub fn state_digest(&self, name: &str) -> Option<Hash> {
+        let st = self.nstates.get(name)?;
+        let serval = bincode::serialize(&("state", name, st)).unwrap();
+        Some(self.hash_algo.calculate(&serval[..]))
+    }
+
+    /// store a signature without verifying it
+    pub fn add_signature(&mut self, subject: Hash, signer: SignerId, sig: Signature) {
+        self.signatures
+            .entry(subject)
+            .or_default()
+            .insert(signer, sig);
+    }
+
+    /// copy the signatures of all events of `other` which are present in `self`
+    pub fn merge_signatures_from(&mut self, other: &Graph<Arg>) {
+        for (h, sigs) in &other.signatures {
+            if self.events.contains_key(h) {
+                self.signatures
+                    .entry(*h)
+                    .or_default()
+                    .extend(sigs.iter().map(|(k, v)| (*k, *v)));
+            }
+        }
+    }
+
+    /// @returns the subjects of signatures which are neither events
+    /// nor current states (e.g. signatures of outdated states),
+    /// these can be removed from [`Graph::signatures`]
+    pub fn stale_signatures(&self) -> BTreeSet<Hash> {
+        let states: BTreeSet<_> = self
+            .nstates
+            .keys()
+            .filter_map(|name| self.state_digest(name))
+            .collect();
+        self.signatures
+            .keys()
+            .filter(|h| !self.events.contains_key(h) && !states.contains(h))
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(feature = "signatures")]
+mod crypto {
+    use super::*;
+    use crate::{GraphError, GraphStore, IncludeSpec, Keyring};
+    use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+
+    impl From<&VerifyingKey> for SignerId {
+        fn from(key: &VerifyingKey) -> SignerId {
+            SignerId(key.to_bytes())
+        }
+    }
+
+    /// Restrictions on the events accepted by
+    /// [`WorkCache::try_merge_with_policy`](crate::WorkCache::try_merge_with_policy).
+    #[derive(Clone, Debug)]
+    pub struct MergePolicy<'a> {
+        /// foreign events need to be signed by one of these keys
+        pub keyring: &'a Keyring,
+        /// events which are accepted without signature,
+        /// usually the closure of the local state
+        pub local: BTreeSet<Hash>,
+    }
+
+    impl MergePolicy<'_> {
+        /// check the events in the closure of `sts` which aren't part of
+        /// `self.local`, e.g. to check foreign events before they get
+        /// inserted into the local graph.
+        ///
+        /// @returns the first event which isn't signed by a trusted key, if any
+        pub fn check<Arg, G: GraphStore<Arg>>(
+            &self,
+            graph: &G,
+            sts: &BTreeSet<Hash>,
+        ) -> Result<Option<(Hash, SignatureStatus)>, GraphError> {
+            let foreign = graph.calculate_dependencies(
+                self.local.clone(),
+                sts.iter().map(|&h| (h, IncludeSpec::IncludeAll)).collect(),
+            )?;
+            for h in foreign {
+                let sigs = graph.get_signatures(&h)?;
+                match signature_status(&h, Some(&sigs), self.keyring) {
+                    SignatureStatus::Good(_) => {}
+                    status => return Ok(Some((h, status))),
+                }
+            }
+            Ok(None)
+        }
+    }
+
+    /// the message which gets signed for `subject`
+    fn message(subject: &Hash) -> Vec<u8> {
+        subject.to_string().into_bytes()
+    }
+
+    pub fn sign(key: &SigningKey, subject: &Hash) -> (SignerId, Signature) {
+        let sig = key.sign(&message(subject));
+        ((&key.verifying_key()).into(), Signature(sig.to_bytes()))
+    }
+
+    /// check the signatures `sigs` of `subject`, a single invalid
+    /// signature makes the status [`SignatureStatus::Bad`]
+    pub fn signature_status(
+        subject: &Hash,
+        sigs: Option<&Signatures>,
+        keyring: &Keyring,
+    ) -> SignatureStatus {
+        let msg = message(subject);
+        let (mut bad, mut good, mut untrusted) = (Vec::new(), None, Vec::new());
+        for (signer, sig) in sigs.into_iter().flatten() {
+            let valid = VerifyingKey::from_bytes(&signer.0)
+                .map(|key| {
+                    key.verify(&msg, &ed25519_dalek::Signature::from_bytes(&sig.0))
+                        .is_ok()
+                })
+                .unwrap_or(false);
+            if !valid {
+                bad.push(*signer);
+            } else if keyring.trusts(signer) {
+                good = good.or(Some(*signer));
+            } else {
+                untrusted.push(*signer);
+            }
+        }
+        if !bad.is_empty() {
+            SignatureStatus::Bad(bad)
+        } else if let Some(signer) = good {
+            SignatureStatus::Good(signer)
+        } else if !untrusted.is_empty() {
+            SignatureStatus::Untrusted(untrusted)
+        } else {
+            SignatureStatus::Unsigned
+        }
+    }
+
+    impl<Arg> Graph<Arg> {
+        pub fn sign_event(&mut self, key: &SigningKey, evid: Hash) -> Result<(), GraphError> {
+            if !self.events.contains_key(&evid) {
+                return Err(GraphError::DependencyNotFound(evid));
+            }
+            let (signer, sig) = sign(key, &evid);
+            self.add_signature(evid, signer, sig);
+            Ok(())
+        }
+
+        /// sign the current value of the state `name`
+        pub fn sign_state(&mut self, key: &SigningKey, name: &str) -> Result<(), GraphError> {
+            let subject = self.state_digest(name).ok_or(GraphError::DatasetNotFound)?;
+            let (signer, sig) = sign(key, &subject);
+            self.add_signature(subject, signer, sig);
+            Ok(())
+        }
+
+        pub fn signature_status(&self, subject: &Hash, keyring: &Keyring) -> SignatureStatus {
+            signature_status(subject, self.signatures.get(subject), keyring)
+        }
+
+        /// check the signatures of the state `name` and all events in its closure
+        pub fn verify_signatures(
+            &self,
+            name: &str,
+            keyring: &Keyring,
+        ) -> Result<SignatureReport, GraphError> {
+            let st = self.nstates.get(name).ok_or(GraphError::DatasetNotFound)?;
+            let state = self.signature_status(&self.state_digest(name).unwrap(), keyring);
+            let closure = self.calculate_dependencies(
+                Default::default(),
+                st.iter().map(|&h| (h, IncludeSpec::IncludeAll)).collect(),
+            )?;
+            let events = closure
+                .into_iter()
+                .map(|h| (h, self.signature_status(&h, keyring)))
+                .filter(|(_, status)| !matches!(status, SignatureStatus::Good(_)))
+                .collect();
+            Ok(SignatureReport { state, events })
+        }
+    }
+}
+
+#[cfg(feature = "signatures")]
+pub use crypto::{sign, signature_status, MergePolicy};
+
+#[cfg(all(test, feature = "signatures"))]
+mod tests {
+    use super::*;
+    use crate::{Event, Keyring};
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn verify() {
+        let alice = SigningKey::from_bytes(&[1; 32]);
+        let mallory = SigningKey::from_bytes(&[2; 32]);
+        let mut keyring = Keyring::default();
+        keyring.insert((&alice.verifying_key()).into(), "alice".to_string());
+
+        let mut g = Graph::default();
+        let mut ev = |arg: &str, deps: &[Hash]| {
+            g.ensure_event(Event {
+                cmd: 0,
+                arg: arg.to_string(),
+                deps: deps.iter().map(|&h| (h, true)).collect(),
+            })
+            .1
+        };
+        let a = ev("a", &[]);
+        let b = ev("b", &[a]);
+        let c = ev("c", &[b]);
+        g.nstates.insert(String::new(), [c].into_iter().collect());
+
+        g.sign_event(&alice, a).unwrap();
+        g.sign_event(&mallory, b).unwrap();
+        g.sign_event(&alice, c).unwrap();
+        // forge a signature of alice
+        let (_, forged) = sign(&mallory, &c);
+        g.add_signature(c, (&alice.verifying_key()).into(), forged);
+        g.sign_state(&alice, "").unwrap();
+
+        let report = g.verify_signatures("", &keyring).unwrap();
+        assert_eq!(
+            report.state,
+            SignatureStatus::Good((&alice.verifying_key()).into())
+        );
+        let expected: BTreeMap<_, _> = [
+            (
+                b,
+                SignatureStatus::Untrusted(vec![(&mallory.verifying_key()).into()]),
+            ),
+            (
+                c,
+                SignatureStatus::Bad(vec![(&alice.verifying_key()).into()]),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(report.events, expected);
+        assert!(!report.is_ok());
+
+        // a bad signature isn't outweighed by a good one, regardless of the order
+        let bob = SigningKey::from_bytes(&[3; 32]);
+        keyring.insert((&bob.verifying_key()).into(), "bob".to_string());
+        for (good, bad) in [(&alice, &bob), (&bob, &alice)] {
+            g.signatures.remove(&a);
+            g.sign_event(good, a).unwrap();
+            g.add_signature(a, (&bad.verifying_key()).into(), forged);
+            assert_eq!(
+                g.signature_status(&a, &keyring),
+                SignatureStatus::Bad(vec![(&bad.verifying_key()).into()])
+            );
+        }
+
+        // changing the state invalidates its signature
+        g.nstates.insert(String::new(), [b].into_iter().collect());
+        let report = g.verify_signatures("", &keyring).unwrap();
+        assert_eq!(report.state, SignatureStatus::Unsigned);
+        assert_eq!(g.stale_signatures().len(), 1);
+    }
+}
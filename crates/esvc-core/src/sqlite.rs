@@ -0,0 +1,790 @@
+use crate::{
+    container::arg_type_name, Compression, ContainerError, Event, EventMeta, Graph, GraphError,
+    GraphStore, Hash, HashAlgo, Header, IncludeSpec, ReflogEntry, Signature, Signatures, SignerId,
+};
+use core::{fmt, marker::PhantomData};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{de::DeserializeOwned, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+
+// hashes are stored in their string representation,
+// to keep the database usable for ad-hoc queries
+const SCHEMA: &str = "
+CREATE TABLE meta (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+) WITHOUT ROWID;
+CREATE TABLE events (
+    hash TEXT PRIMARY KEY,
+    cmd INTEGER NOT NULL,
+    arg BLOB NOT NULL
+) WITHOUT ROWID;
+CREATE TABLE deps (
+    event TEXT NOT NULL REFERENCES events (hash),
+    dep TEXT NOT NULL REFERENCES events (hash),
+    hard INTEGER NOT NULL,
+    PRIMARY KEY (event, dep)
+) WITHOUT ROWID;
+CREATE INDEX deps_by_dep ON deps (dep);
+CREATE TABLE nstates (
+    name TEXT PRIMARY KEY
+) WITHOUT ROWID;
+CREATE TABLE nstate_members (
+    name TEXT NOT NULL REFERENCES nstates (name),
+    member TEXT NOT NULL REFERENCES events (hash),
+    PRIMARY KEY (name, member)
+) WITHOUT ROWID;
+";
+
+// tables which were added later, thus also created when opening older databases
+const ADDED_SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS reflog (
+    seq INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    entry BLOB NOT NULL
+);
+CREATE TABLE IF NOT EXISTS event_meta (
+    hash TEXT PRIMARY KEY REFERENCES events (hash),
+    author TEXT,
+    timestamp INTEGER,
+    message TEXT
+) WITHOUT ROWID;
+CREATE TABLE IF NOT EXISTS signatures (
+    subject TEXT NOT NULL,
+    signer TEXT NOT NULL,
+    signature BLOB NOT NULL,
+    PRIMARY KEY (subject, signer)
+) WITHOUT ROWID;
+";
+
+fn storage_err(e: impl fmt::Display) -> GraphError {
+    GraphError::Storage(e.to_string())
+}
+
+fn parse_hash(s: String) -> Result<Hash, GraphError> {
+    s.parse()
+        .map_err(|e| GraphError::Storage(format!("invalid hash '{}' in database: {}", s, e)))
+}
+
+fn parse_signer(s: String) -> Result<SignerId, GraphError> {
+    s.parse()
+        .map_err(|e| GraphError::Storage(format!("invalid signer '{}' in database: {}", s, e)))
+}
+
+fn parse_sig(sig: Vec<u8>) -> Result<Signature, GraphError> {
+    sig.try_into()
+        .map(Signature)
+        .map_err(|_| storage_err("invalid signature length in database"))
+}
+
+/// A [`GraphStore`] which keeps the graph in a SQLite database.
+///
+/// Every modification is committed immediately (use
+/// [`SqliteStore::transaction`] to group modifications), which makes
+/// this store crash-safe. Dependencies are enforced via foreign keys,
+/// so events have to be inserted after their dependencies.
+pub struct SqliteStore<Arg> {
+    conn: Connection,
+    hash_algo: HashAlgo,
+    _arg: PhantomData<fn() -> Arg>,
+}
+
+impl<Arg> SqliteStore<Arg> {
+    /// initialize a new store in an empty database
+    pub fn create(
+        mut conn: Connection,
+        engine: &str,
+        hash_algo: HashAlgo,
+    ) -> Result<Self, ContainerError> {
+        conn.pragma_update(None, "foreign_keys", true)?;
+        let tx = conn.transaction()?;
+        tx.execute_batch(SCHEMA)?;
+        tx.execute_batch(ADDED_SCHEMA)?;
+        {
+            let mut stmt = tx.prepare("INSERT INTO meta (key, value) VALUES (?1, ?2)")?;
+            stmt.execute(["engine", engine])?;
+            stmt.execute(["arg_type", &arg_type_name::<Arg>()])?;
+            stmt.execute(["hash_algo", &hash_algo.to_string()])?;
+        }
+        tx.commit()?;
+        Ok(Self {
+            conn,
+            hash_algo,
+            _arg: PhantomData,
+        })
+    }
+
+    /// open an existing store, which must have been written for `engine`
+    pub fn open(conn: Connection, engine: &str) -> Result<Self, ContainerError> {
+        conn.pragma_update(None, "foreign_keys", true)?;
+        let has_meta: bool = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'meta')",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_meta {
+            return Err(ContainerError::InvalidMagic);
+        }
+        let get = |key: &str| {
+            conn.query_row("SELECT value FROM meta WHERE key = ?1", [key], |row| {
+                row.get::<_, String>(0)
+            })
+            .optional()?
+            .ok_or(ContainerError::InvalidMagic)
+        };
+        let header = Header {
+            engine: get("engine")?,
+            arg_type: get("arg_type")?,
+            hash_algo: get("hash_algo")?
+                .parse()
+                .map_err(|_| ContainerError::InvalidMagic)?,
+            compression: Compression::None,
+        };
+        header.check(engine)?;
+        conn.execute_batch(ADDED_SCHEMA)?;
+        Ok(Self {
+            conn,
+            hash_algo: header.hash_algo,
+            _arg: PhantomData,
+        })
+    }
+
+    /// the underlying database connection, e.g. for ad-hoc queries
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    pub fn into_inner(self) -> Connection {
+        self.conn
+    }
+
+    /// run `f` inside of a transaction, which gets rolled back if `f` fails.
+    /// transactions can be nested.
+    pub fn transaction<T, E>(&mut self, f: impl FnOnce(&mut Self) -> Result<T, E>) -> Result<T, E>
+    where
+        E: From<GraphError>,
+    {
+        self.conn
+            .execute_batch("SAVEPOINT esvc")
+            .map_err(storage_err)?;
+        match f(self) {
+            Ok(x) => {
+                self.conn
+                    .execute_batch("RELEASE esvc")
+                    .map_err(storage_err)?;
+                Ok(x)
+            }
+            Err(e) => {
+                // the original error is more interesting than a failed rollback
+                let _ = self.conn.execute_batch("ROLLBACK TO esvc; RELEASE esvc");
+                Err(e)
+            }
+        }
+    }
+
+    fn get_deps_inner(&self, evid: &Hash) -> Result<BTreeMap<Hash, bool>, GraphError> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT dep, hard FROM deps WHERE event = ?1")
+            .map_err(storage_err)?;
+        let rows = stmt
+            .query_map([evid.to_string()], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?))
+            })
+            .map_err(storage_err)?;
+        rows.map(|row| {
+            let (dep, is_hard) = row.map_err(storage_err)?;
+            Ok((parse_hash(dep)?, is_hard))
+        })
+        .collect()
+    }
+
+    /// replace the metadata of an event
+    fn put_meta(&self, evid: &Hash, meta: &EventMeta) -> Result<(), GraphError> {
+        self.conn
+            .prepare_cached(
+                "INSERT OR REPLACE INTO event_meta (hash, author, timestamp, message)
+                VALUES (?1, ?2, ?3, ?4)",
+            )
+            .map_err(storage_err)?
+            .execute(params![
+                evid.to_string(),
+                meta.author,
+                meta.timestamp,
+                meta.message
+            ])
+            .map_err(storage_err)?;
+        Ok(())
+    }
+}
+
+impl<Arg> SqliteStore<Arg>
+where
+    Arg: Serialize + DeserializeOwned,
+{
+    /// load the whole graph into memory
+    pub fn load_graph(&self) -> Result<Graph<Arg>, GraphError> {
+        let mut g = Graph::with_hash_algo(self.hash_algo);
+        let mut stmt = self
+            .conn
+            .prepare("SELECT hash, cmd, arg FROM events")
+            .map_err(storage_err)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, u32>(1)?,
+                    row.get::<_, Vec<u8>>(2)?,
+                ))
+            })
+            .map_err(storage_err)?;
+        for row in rows {
+            let (h, cmd, arg) = row.map_err(storage_err)?;
+            g.events.insert(
+                parse_hash(h)?,
+                Event {
+                    cmd,
+                    arg: bincode::deserialize(&arg[..]).map_err(storage_err)?,
+                    deps: BTreeMap::new(),
+                },
+            );
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT event, dep, hard FROM deps")
+            .map_err(storage_err)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, bool>(2)?,
+                ))
+            })
+            .map_err(storage_err)?;
+        for row in rows {
+            let (h, dep, is_hard) = row.map_err(storage_err)?;
+            let h = parse_hash(h)?;
+            g.events
+                .get_mut(&h)
+                .ok_or_else(|| storage_err(format!("dependency entry of missing event {}", h)))?
+                .deps
+                .insert(parse_hash(dep)?, is_hard);
+        }
+        g.rebuild_dependents();
+
+        for name in self.nstate_names()? {
+            let st = self.get_nstate(&name)?.unwrap_or_default();
+            g.nstates.insert(name, st);
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT entry FROM reflog ORDER BY seq")
+            .map_err(storage_err)?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, Vec<u8>>(0))
+            .map_err(storage_err)?;
+        for row in rows {
+            let entry = row.map_err(storage_err)?;
+            g.reflog
+                .push(bincode::deserialize(&entry[..]).map_err(storage_err)?);
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT hash, author, timestamp, message FROM event_meta")
+            .map_err(storage_err)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    EventMeta {
+                        author: row.get(1)?,
+                        timestamp: row.get(2)?,
+                        message: row.get(3)?,
+                    },
+                ))
+            })
+            .map_err(storage_err)?;
+        for row in rows {
+            let (h, meta) = row.map_err(storage_err)?;
+            g.meta.insert(parse_hash(h)?, meta);
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT subject, signer, signature FROM signatures")
+            .map_err(storage_err)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Vec<u8>>(2)?,
+                ))
+            })
+            .map_err(storage_err)?;
+        for row in rows {
+            let (subject, signer, sig) = row.map_err(storage_err)?;
+            g.add_signature(parse_hash(subject)?, parse_signer(signer)?, parse_sig(sig)?);
+        }
+        Ok(g)
+    }
+
+    /// append the entries of `reflog` which aren't stored yet,
+    /// and delete the expired ones
+    fn sync_reflog(&mut self, reflog: &[ReflogEntry]) -> Result<(), GraphError> {
+        if let Some(first) = reflog.first() {
+            self.conn
+                .prepare_cached("DELETE FROM reflog WHERE seq < ?1")
+                .map_err(storage_err)?
+                .execute([first.seq])
+                .map_err(storage_err)?;
+        }
+        let next: u64 = self
+            .conn
+            .query_row("SELECT coalesce(max(seq) + 1, 0) FROM reflog", [], |row| {
+                row.get(0)
+            })
+            .map_err(storage_err)?;
+        let mut stmt = self
+            .conn
+            .prepare_cached("INSERT INTO reflog (seq, name, entry) VALUES (?1, ?2, ?3)")
+            .map_err(storage_err)?;
+        for e in reflog.iter().filter(|e| e.seq >= next) {
+            let entry = bincode::serialize(e).map_err(storage_err)?;
+            stmt.execute(params![e.seq, e.name, entry])
+                .map_err(storage_err)?;
+        }
+        Ok(())
+    }
+
+    /// delete all events (including their metadata and signatures)
+    /// which aren't present in `g`. they mustn't be referenced by any
+    /// named state in the database anymore.
+    fn delete_removed(&mut self, g: &Graph<Arg>) -> Result<(), GraphError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT hash FROM events")
+            .map_err(storage_err)?;
+        let removed = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(storage_err)?
+            .filter_map(|row| match row.map_err(storage_err).and_then(parse_hash) {
+                Ok(h) if g.events.contains_key(&h) => None,
+                x => Some(x),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        drop(stmt);
+
+        // the dependencies of all removed events have to be deleted first,
+        // because removed events might depend on each other
+        for query in [
+            "DELETE FROM deps WHERE event = ?1",
+            "DELETE FROM event_meta WHERE hash = ?1",
+            "DELETE FROM signatures WHERE subject = ?1",
+        ] {
+            let mut stmt = self.conn.prepare_cached(query).map_err(storage_err)?;
+            for h in &removed {
+                stmt.execute([h.to_string()]).map_err(storage_err)?;
+            }
+        }
+        let mut stmt = self
+            .conn
+            .prepare_cached("DELETE FROM events WHERE hash = ?1")
+            .map_err(storage_err)?;
+        for h in &removed {
+            stmt.execute([h.to_string()]).map_err(storage_err)?;
+        }
+        Ok(())
+    }
+
+    /// store everything which is present in `g`, but not in the database yet,
+    /// replace all named states and the metadata of events,
+    /// append new reflog entries, and delete events which aren't present
+    /// in `g` anymore (e.g. because of [`Graph::gc`]), inside of a single transaction.
+    pub fn sync(&mut self, g: &Graph<Arg>) -> Result<(), GraphError>
+    where
+        Arg: Clone,
+    {
+        self.transaction(|this| {
+            let mut present = BTreeSet::new();
+            let mut missing = BTreeMap::new();
+            for &h in g.events.keys() {
+                if this.contains_event(&h)? {
+                    present.insert(h);
+                } else {
+                    missing.insert(h, IncludeSpec::IncludeAll);
+                }
+            }
+            // the dependencies have to be inserted first, because of the foreign keys
+            for h in g.calculate_dependencies(present, missing)? {
+                this.insert_event(h, g.events[&h].clone())?;
+            }
+            for name in this.nstate_names()? {
+                if !g.nstates.contains_key(&name) {
+                    this.remove_nstate(&name)?;
+                }
+            }
+            for (name, st) in &g.nstates {
+                if this.get_nstate(name)?.as_ref() != Some(st) {
+                    this.set_nstate(name, st.clone())?;
+                }
+            }
+            this.delete_removed(g)?;
+            for (h, meta) in &g.meta {
+                if this.get_meta(h)?.as_ref() != Some(meta) {
+                    this.put_meta(h, meta)?;
+                }
+            }
+            for (subject, sigs) in &g.signatures {
+                if &this.get_signatures(subject)? != sigs {
+                    for (signer, sig) in sigs {
+                        this.add_signature(*subject, *signer, *sig)?;
+                    }
+                }
+            }
+            this.sync_reflog(&g.reflog)
+        })
+    }
+}
+
+impl<Arg> GraphStore<Arg> for SqliteStore<Arg>
+where
+    Arg: Serialize + DeserializeOwned,
+{
+    type EventRef<'a>
+        = Box<Event<Arg>>
+    where
+        Self: 'a;
+
+    fn hash_algo(&self) -> HashAlgo {
+        self.hash_algo
+    }
+
+    fn get_event(&self, evid: &Hash) -> Result<Option<Box<Event<Arg>>>, GraphError> {
+        let row = self
+            .conn
+            .prepare_cached("SELECT cmd, arg FROM events WHERE hash = ?1")
+            .map_err(storage_err)?
+            .query_row([evid.to_string()], |row| {
+                Ok((row.get::<_, u32>(0)?, row.get::<_, Vec<u8>>(1)?))
+            })
+            .optional()
+            .map_err(storage_err)?;
+        let (cmd, arg) = match row {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+        Ok(Some(Box::new(Event {
+            cmd,
+            arg: bincode::deserialize(&arg[..]).map_err(storage_err)?,
+            deps: self.get_deps_inner(evid)?,
+        })))
+    }
+
+    fn get_deps(&self, evid: &Hash) -> Result<Option<Cow<'_, BTreeMap<Hash, bool>>>, GraphError> {
+        Ok(if self.contains_event(evid)? {
+            Some(Cow::Owned(self.get_deps_inner(evid)?))
+        } else {
+            None
+        })
+    }
+
+    fn contains_event(&self, evid: &Hash) -> Result<bool, GraphError> {
+        self.conn
+            .prepare_cached("SELECT EXISTS (SELECT 1 FROM events WHERE hash = ?1)")
+            .map_err(storage_err)?
+            .query_row([evid.to_string()], |row| row.get(0))
+            .map_err(storage_err)
+    }
+
+    fn insert_event(&mut self, evid: Hash, ev: Event<Arg>) -> Result<(), GraphError> {
+        let arg = bincode::serialize(&ev.arg).map_err(storage_err)?;
+        let evid = evid.to_string();
+        self.transaction(|this| {
+            this.conn
+                .prepare_cached("INSERT INTO events (hash, cmd, arg) VALUES (?1, ?2, ?3)")
+                .map_err(storage_err)?
+                .execute(params![evid, ev.cmd, arg])
+                .map_err(storage_err)?;
+            let mut stmt = this
+                .conn
+                .prepare_cached("INSERT INTO deps (event, dep, hard) VALUES (?1, ?2, ?3)")
+                .map_err(storage_err)?;
+            for (dep, is_hard) in &ev.deps {
+                stmt.execute(params![evid, dep.to_string(), is_hard])
+                    .map_err(storage_err)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn nstate_names(&self) -> Result<Vec<String>, GraphError> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT name FROM nstates ORDER BY name")
+            .map_err(storage_err)?;
+        let rows = stmt.query_map([], |row| row.get(0)).map_err(storage_err)?;
+        rows.map(|row| row.map_err(storage_err)).collect()
+    }
+
+    fn get_nstate(&self, name: &str) -> Result<Option<BTreeSet<Hash>>, GraphError> {
+        let exists: bool = self
+            .conn
+            .prepare_cached("SELECT EXISTS (SELECT 1 FROM nstates WHERE name = ?1)")
+            .map_err(storage_err)?
+            .query_row([name], |row| row.get(0))
+            .map_err(storage_err)?;
+        if !exists {
+            return Ok(None);
+        }
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT member FROM nstate_members WHERE name = ?1")
+            .map_err(storage_err)?;
+        let rows = stmt
+            .query_map([name], |row| row.get::<_, String>(0))
+            .map_err(storage_err)?;
+        rows.map(|row| parse_hash(row.map_err(storage_err)?))
+            .collect::<Result<_, _>>()
+            .map(Some)
+    }
+
+    fn set_nstate(&mut self, name: &str, st: BTreeSet<Hash>) -> Result<(), GraphError> {
+        self.transaction(|this| {
+            this.conn
+                .execute("INSERT OR IGNORE INTO nstates (name) VALUES (?1)", [name])
+                .map_err(storage_err)?;
+            this.conn
+                .execute("DELETE FROM nstate_members WHERE name = ?1", [name])
+                .map_err(storage_err)?;
+            let mut stmt = this
+                .conn
+                .prepare_cached("INSERT INTO nstate_members (name, member) VALUES (?1, ?2)")
+                .map_err(storage_err)?;
+            for h in &st {
+                stmt.execute([name, &h.to_string()]).map_err(storage_err)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn remove_nstate(&mut self, name: &str) -> Result<Option<BTreeSet<Hash>>, GraphError> {
+        self.transaction(|this| {
+            let ret = this.get_nstate(name)?;
+            if ret.is_some() {
+                this.conn
+                    .execute("DELETE FROM nstate_members WHERE name = ?1", [name])
+                    .map_err(storage_err)?;
+                this.conn
+                    .execute("DELETE FROM nstates WHERE name = ?1", [name])
+                    .map_err(storage_err)?;
+            }
+            Ok(ret)
+        })
+    }
+
+    fn get_meta(&self, evid: &Hash) -> Result<Option<EventMeta>, GraphError> {
+        self.conn
+            .prepare_cached("SELECT author, timestamp, message FROM event_meta WHERE hash = ?1")
+            .map_err(storage_err)?
+            .query_row([evid.to_string()], |row| {
+                Ok(EventMeta {
+                    author: row.get(0)?,
+                    timestamp: row.get(1)?,
+                    message: row.get(2)?,
+                })
+            })
+            .optional()
+            .map_err(storage_err)
+    }
+
+    fn merge_meta(&mut self, evid: Hash, meta: EventMeta) -> Result<(), GraphError> {
+        self.transaction(|this| {
+            if !this.contains_event(&evid)? {
+                return Err(GraphError::DependencyNotFound(evid));
+            }
+            let merged = match this.get_meta(&evid)? {
+                Some(old) => old.merge(meta),
+                None => meta,
+            };
+            if !merged.is_empty() {
+                this.put_meta(&evid, &merged)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn get_signatures(&self, subject: &Hash) -> Result<Signatures, GraphError> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT signer, signature FROM signatures WHERE subject = ?1")
+            .map_err(storage_err)?;
+        let rows = stmt
+            .query_map([subject.to_string()], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?))
+            })
+            .map_err(storage_err)?;
+        rows.map(|row| {
+            let (signer, sig) = row.map_err(storage_err)?;
+            Ok((parse_signer(signer)?, parse_sig(sig)?))
+        })
+        .collect()
+    }
+
+    fn add_signature(
+        &mut self,
+        subject: Hash,
+        signer: SignerId,
+        sig: Signature,
+    ) -> Result<(), GraphError> {
+        self.conn
+            .prepare_cached(
+                "INSERT OR REPLACE INTO signatures (subject, signer, signature)
+                VALUES (?1, ?2, ?3)",
+            )
+            .map_err(storage_err)?
+            .execute(params![subject.to_string(), signer.to_string(), &sig.0[..]])
+            .map_err(storage_err)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReflogReason;
+
+    fn ev(arg: &str, deps: &[(Hash, bool)]) -> Event<String> {
+        Event {
+            cmd: 0,
+            arg: arg.to_string(),
+            deps: deps.iter().copied().collect(),
+        }
+    }
+
+    fn mkstore() -> SqliteStore<String> {
+        SqliteStore::create(
+            Connection::open_in_memory().unwrap(),
+            "test",
+            HashAlgo::Sha256,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut s = mkstore();
+        let (_, a) = s.ensure_event(ev("a", &[])).unwrap();
+        let (_, b) = s.ensure_event(ev("b", &[(a, false)])).unwrap();
+        assert_eq!(s.ensure_event(ev("b", &[(a, false)])).unwrap(), (None, b));
+        s.set_nstate("x", [a].into_iter().collect()).unwrap();
+        s.set_nstate("", [b].into_iter().collect()).unwrap();
+        s.set_nstate("empty", BTreeSet::new()).unwrap();
+        assert!(s.remove_nstate("x").unwrap().is_some());
+        assert_eq!(s.remove_nstate("x").unwrap(), None);
+
+        let s = SqliteStore::<String>::open(s.into_inner(), "test").unwrap();
+        assert_eq!(s.hash_algo(), HashAlgo::Sha256);
+        assert_eq!(*s.get_event(&b).unwrap().unwrap(), ev("b", &[(a, false)]));
+        assert_eq!(s.get_event(&a.algo().calculate(b"")).unwrap(), None);
+        assert_eq!(s.get_nstate("empty").unwrap(), Some(BTreeSet::new()));
+        assert_eq!(
+            s.nstate_names().unwrap(),
+            vec![String::new(), "empty".to_string()]
+        );
+
+        // the graph is accessible via plain SQL
+        let dependents: String = s
+            .connection()
+            .query_row(
+                "SELECT event FROM deps WHERE dep = ?1",
+                [a.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(dependents, b.to_string());
+
+        let g = s.load_graph().unwrap();
+        assert_eq!(g.verify(), Vec::new());
+        assert_eq!(g.events.len(), 2);
+        assert_eq!(g.nstates.len(), 2);
+
+        assert!(matches!(
+            SqliteStore::<String>::open(s.into_inner(), "other"),
+            Err(ContainerError::EngineMismatch { .. })
+        ));
+        assert!(matches!(
+            SqliteStore::<String>::open(Connection::open_in_memory().unwrap(), "test"),
+            Err(ContainerError::InvalidMagic)
+        ));
+    }
+
+    #[test]
+    fn transaction_rollback() {
+        let mut s = mkstore();
+        let (_, a) = s.ensure_event(ev("a", &[])).unwrap();
+        let res: Result<(), GraphError> = s.transaction(|this| {
+            this.ensure_event(ev("b", &[(a, true)]))?;
+            this.set_nstate("", [a].into_iter().collect())?;
+            Err(GraphError::DatasetNotFound)
+        });
+        assert!(res.is_err());
+        assert_eq!(s.load_graph().unwrap().events.len(), 1);
+        assert_eq!(s.nstate_names().unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn sync() {
+        let mut g = Graph::with_hash_algo(HashAlgo::Sha256);
+        let (_, a) = g.ensure_event(ev("a", &[]));
+        let (_, b) = g.ensure_event(ev("b", &[(a, true)]));
+        g.set_state("x", [a].into_iter().collect(), ReflogReason::Manual);
+        g.set_state("", [b].into_iter().collect(), ReflogReason::Shelve);
+        g.merge_meta(
+            a,
+            EventMeta {
+                author: Some("alice".to_string()),
+                timestamp: Some(1),
+                message: None,
+            },
+        )
+        .unwrap();
+
+        let mut s = mkstore();
+        s.sync(&g).unwrap();
+        assert_eq!(s.load_graph().unwrap(), g);
+
+        g.remove_state("x", ReflogReason::Manual);
+        g.merge_meta(a, EventMeta::now()).unwrap();
+        g.add_signature(a, SignerId([1; 32]), Signature([2; 64]));
+        s.merge_meta(
+            a,
+            EventMeta {
+                message: Some("initial".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(s.get_meta(&a).unwrap().unwrap().message.unwrap(), "initial");
+        g.ensure_event(ev("c", &[(b, true)]));
+        s.sync(&g).unwrap();
+        assert_eq!(s.load_graph().unwrap(), g);
+
+        // events removed by gc get deleted
+        g.expire_reflog(1);
+        let (_, d) = g.ensure_event(ev("d", &[]));
+        let (_, e) = g.ensure_event(ev("e", &[(d, true)]));
+        g.merge_meta(e, EventMeta::now()).unwrap();
+        g.add_signature(e, SignerId([1; 32]), Signature([2; 64]));
+        s.sync(&g).unwrap();
+        assert_eq!(g.gc(false).unwrap().len(), 3);
+        s.sync(&g).unwrap();
+        assert_eq!(s.load_graph().unwrap(), g);
+        assert!(!s.contains_event(&e).unwrap());
+    }
+}
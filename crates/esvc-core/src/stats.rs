@@ -0,0 +1,152 @@
+use crate::{Graph, GraphError, GraphStore, Hash, IncludeSpec};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// The result of [`Graph::stats`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphStats {
+    pub events: usize,
+    pub events_per_cmd: BTreeMap<u32, usize>,
+    pub hard_edges: usize,
+    pub soft_edges: usize,
+    /// the length of the longest dependency chain (in events)
+    pub max_depth: usize,
+    /// number of heads of each named state, merges get slower
+    /// the wider a state gets
+    pub state_widths: BTreeMap<String, usize>,
+    /// events which aren't reachable from any named state or the reflog,
+    /// and would thus be removed by [`Graph::gc`]
+    pub orphans: BTreeSet<Hash>,
+    pub avg_deps: f64,
+}
+
+impl<Arg> Graph<Arg> {
+    pub fn stats(&self) -> Result<GraphStats, GraphError> {
+        let mut events_per_cmd = BTreeMap::new();
+        let (mut hard_edges, mut soft_edges) = (0, 0);
+        for ev in self.events.values() {
+            *events_per_cmd.entry(ev.cmd).or_insert(0) += 1;
+            for &is_hard in ev.deps.values() {
+                if is_hard {
+                    hard_edges += 1;
+                } else {
+                    soft_edges += 1;
+                }
+            }
+        }
+
+        // dependencies come first in this order
+        let order = self.calculate_dependencies(
+            Default::default(),
+            self.events
+                .keys()
+                .map(|&h| (h, IncludeSpec::IncludeAll))
+                .collect(),
+        )?;
+        let mut depths = BTreeMap::<Hash, usize>::new();
+        for h in order {
+            let depth = 1 + self.events[&h]
+                .deps
+                .keys()
+                .map(|dep| depths[dep])
+                .max()
+                .unwrap_or(0);
+            depths.insert(h, depth);
+        }
+
+        let state_widths = self
+            .nstates
+            .iter()
+            .map(|(name, st)| {
+                let heads = self.fold_state(st.iter().map(|&h| (h, false)).collect(), false)?;
+                Ok((name.clone(), heads.len()))
+            })
+            .collect::<Result<_, GraphError>>()?;
+
+        let reachable = self.reachable_events()?;
+        let orphans = self
+            .events
+            .keys()
+            .filter(|h| !reachable.contains(h))
+            .copied()
+            .collect();
+
+        let events = self.events.len();
+        Ok(GraphStats {
+            events,
+            events_per_cmd,
+            hard_edges,
+            soft_edges,
+            max_depth: depths.into_values().max().unwrap_or(0),
+            state_widths,
+            orphans,
+            avg_deps: if events == 0 {
+                0.0
+            } else {
+                (hard_edges + soft_edges) as f64 / events as f64
+            },
+        })
+    }
+}
+
+impl fmt::Display for GraphStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "events: {}", self.events)?;
+        for (cmd, n) in &self.events_per_cmd {
+            writeln!(f, "  cmd {}: {}", cmd, n)?;
+        }
+        writeln!(
+            f,
+            "edges: {} hard, {} soft ({:.2} per event)",
+            self.hard_edges, self.soft_edges, self.avg_deps
+        )?;
+        writeln!(f, "max depth: {}", self.max_depth)?;
+        for (name, width) in &self.state_widths {
+            writeln!(f, "state {:?}: {} heads", name, width)?;
+        }
+        writeln!(f, "orphans: {}", self.orphans.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Event;
+
+    #[test]
+    fn stats() {
+        let mut g = Graph::default();
+        let mut ev = |cmd: u32, arg: &str, deps: &[(Hash, bool)]| {
+            g.ensure_event(Event {
+                cmd,
+                arg: arg.to_string(),
+                deps: deps.iter().copied().collect(),
+            })
+            .1
+        };
+        let a = ev(0, "a", &[]);
+        let b = ev(0, "b", &[(a, true)]);
+        let c = ev(1, "c", &[(b, true), (a, false)]);
+        let d = ev(1, "d", &[]);
+        let e = ev(0, "e", &[]);
+        g.nstates
+            .insert(String::new(), [a, b, c, d].into_iter().collect());
+        g.nstates.insert("x".to_string(), [a].into_iter().collect());
+
+        let st = g.stats().unwrap();
+        assert_eq!(st.events, 5);
+        assert_eq!(
+            st.events_per_cmd,
+            [(0, 3), (1, 2)].into_iter().collect::<BTreeMap<_, _>>()
+        );
+        assert_eq!((st.hard_edges, st.soft_edges), (2, 1));
+        assert_eq!(st.max_depth, 3);
+        assert_eq!(st.state_widths[""], 2);
+        assert_eq!(st.state_widths["x"], 1);
+        assert_eq!(st.orphans, [e].into_iter().collect());
+        assert!((st.avg_deps - 0.6).abs() < 1e-9);
+
+        let empty = Graph::<String>::default().stats().unwrap();
+        assert_eq!((empty.events, empty.max_depth, empty.avg_deps), (0, 0, 0.0));
+    }
+}
@@ -0,0 +1,446 @@
+use crate::{
+    Event, EventMeta, Graph, GraphError, Hash, HashAlgo, IncludeSpec, Signature, Signatures,
+    SignerId,
+};
+use std::borrow::Cow;
+use std::collections::{btree_map::Entry, BTreeMap, BTreeSet};
+use std::ops::Deref;
+
+/// A cache for [`GraphStore::fold_state_cached`], which keeps the closure
+/// of the last folded state. Because events are immutable, it stays valid
+/// for any store which contains the same events, but it needs to be cleared
+/// once events are removed. [`Graph::fold_state_memo`] takes care of that.
+#[derive(Clone, Debug, Default)]
+pub struct ClosureCache {
+    /// evid -> is a dependency of another event in the closure
+    closure: BTreeMap<Hash, bool>,
+    /// the events of `closure` which aren't dependencies
+    heads: BTreeSet<Hash>,
+}
+
+impl ClosureCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.closure.clear();
+        self.heads.clear();
+    }
+
+    /// the number of cached events
+    pub fn len(&self) -> usize {
+        self.closure.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.closure.is_empty()
+    }
+}
+
+/// A storage backend for a graph, which allows graphs to not be kept
+/// completely in memory. [`Graph`] is the in-memory implementation.
+pub trait GraphStore<Arg> {
+    /// a (possibly owned) reference to an event
+    type EventRef<'a>: Deref<Target = Event<Arg>>
+    where
+        Self: 'a;
+
+    /// the algorithm used to hash newly inserted events
+    fn hash_algo(&self) -> HashAlgo;
+
+    fn get_event(&self, evid: &Hash) -> Result<Option<Self::EventRef<'_>>, GraphError>;
+
+    /// get the dependencies of an event, which might be cheaper than
+    /// retrieving the whole event
+    fn get_deps(&self, evid: &Hash) -> Result<Option<Cow<'_, BTreeMap<Hash, bool>>>, GraphError> {
+        Ok(self.get_event(evid)?.map(|ev| Cow::Owned(ev.deps.clone())))
+    }
+
+    fn contains_event(&self, evid: &Hash) -> Result<bool, GraphError> {
+        Ok(self.get_deps(evid)?.is_some())
+    }
+
+    /// insert an event without any checks, `evid` must be the hash of `ev`,
+    /// and the store must not contain any event with that hash yet.
+    /// use [`GraphStore::ensure_event`] instead if unsure.
+    fn insert_event(&mut self, evid: Hash, ev: Event<Arg>) -> Result<(), GraphError>;
+
+    fn nstate_names(&self) -> Result<Vec<String>, GraphError>;
+
+    fn get_nstate(&self, name: &str) -> Result<Option<BTreeSet<Hash>>, GraphError>;
+
+    fn set_nstate(&mut self, name: &str, st: BTreeSet<Hash>) -> Result<(), GraphError>;
+
+    fn remove_nstate(&mut self, name: &str) -> Result<Option<BTreeSet<Hash>>, GraphError>;
+
+    fn get_meta(&self, evid: &Hash) -> Result<Option<EventMeta>, GraphError>;
+
+    /// merge `meta` into the metadata of an existing event
+    /// (see [`EventMeta::merge`])
+    fn merge_meta(&mut self, evid: Hash, meta: EventMeta) -> Result<(), GraphError>;
+
+    /// get the signatures of an event or state digest
+    /// (see [`Graph::state_digest`])
+    fn get_signatures(&self, subject: &Hash) -> Result<Signatures, GraphError>;
+
+    /// store a signature without verifying it
+    fn add_signature(
+        &mut self,
+        subject: Hash,
+        signer: SignerId,
+        sig: Signature,
+    ) -> Result<(), GraphError>;
+
+    /// get-or-insert event, check if it matches
+    ///
+    /// @returns (Some(@arg ev) if collision else None, Hash of @arg ev)
+    fn ensure_event(&mut self, ev: Event<Arg>) -> Result<(Option<Event<Arg>>, Hash), GraphError>
+    where
+        Arg: esvc_traits::CommandArg,
+    {
+        let serval = bincode::serialize::<Event<Arg>>(&ev).unwrap();
+        let h = self.hash_algo().calculate(&serval[..]);
+        let matches = self.get_event(&h)?.map(|x| *x == ev);
+        Ok(match matches {
+            Some(true) => (None, h),
+            Some(false) => (Some(ev), h),
+            None => {
+                self.insert_event(h, ev)?;
+                (None, h)
+            }
+        })
+    }
+
+    /// fold a state, expanding of compressing it along the dependencies.
+    /// `st` entries should be initialized to `false` when creating a state from a `BTreeSet<Hash>`.
+    fn fold_state(
+        &self,
+        mut st: BTreeMap<Hash, bool>,
+        expand: bool,
+    ) -> Result<BTreeMap<Hash, bool>, GraphError> {
+        // each event is visited once, when it gets added to `st`
+        let mut todo: Vec<Hash> = st.keys().copied().collect();
+        while let Some(h) = todo.pop() {
+            let deps = self
+                .get_deps(&h)?
+                .ok_or(GraphError::DependencyNotFound(h))?;
+            for &j in deps.keys() {
+                if st.insert(j, true).is_none() {
+                    todo.push(j);
+                }
+            }
+        }
+        if !expand {
+            // keep only non-dependencies
+            st.retain(|_, is_dep| !*is_dep);
+        }
+        Ok(st)
+    }
+
+    /// like [`GraphStore::fold_state`], but reuses the closure computed by the
+    /// previous call with the same `cache`, as long as the resulting heads
+    /// of that call are part of `st`. this makes repeatedly folding a growing
+    /// state (e.g. after each shelved event) proportional to the new events.
+    fn fold_state_cached(
+        &self,
+        st: BTreeMap<Hash, bool>,
+        expand: bool,
+        cache: &mut ClosureCache,
+    ) -> Result<BTreeMap<Hash, bool>, GraphError> {
+        if !cache.heads.iter().all(|h| st.contains_key(h)) {
+            cache.clear();
+        }
+
+        let mut todo = Vec::new();
+        for &h in st.keys() {
+            if let Entry::Vacant(e) = cache.closure.entry(h) {
+                e.insert(false);
+                todo.push(h);
+            }
+        }
+        while let Some(h) = todo.pop() {
+            let deps = match self.get_deps(&h) {
+                Ok(Some(deps)) => deps,
+                // don't keep an incomplete closure around
+                Ok(None) => {
+                    cache.clear();
+                    return Err(GraphError::DependencyNotFound(h));
+                }
+                Err(e) => {
+                    cache.clear();
+                    return Err(e);
+                }
+            };
+            for &j in deps.keys() {
+                if cache.closure.insert(j, true).is_none() {
+                    todo.push(j);
+                }
+            }
+        }
+
+        // only seeds can be heads, all other events were reached
+        cache.heads = st.keys().filter(|h| !cache.closure[h]).copied().collect();
+        Ok(if expand {
+            let mut ret = cache.closure.clone();
+            ret.extend(st.into_iter().filter(|&(_, is_dep)| is_dep));
+            ret
+        } else {
+            // entries which are initialized to `true` are only excluded
+            // from the result, they don't influence the cache.
+            cache
+                .heads
+                .iter()
+                .filter(|h| !st[h])
+                .map(|&h| (h, false))
+                .collect()
+        })
+    }
+
+    fn calculate_dependencies(
+        &self,
+        mut tt: BTreeSet<Hash>,
+        evids: BTreeMap<Hash, IncludeSpec>,
+    ) -> Result<Vec<Hash>, GraphError> {
+        let mut ret = Vec::new();
+
+        // heap of necessary dependencies
+        let mut deps = Vec::new();
+
+        // events which wait for their dependencies to be satisfied,
+        // each entry is a dependency of the previous one
+        let mut pending = Vec::new();
+        let mut pending_set = BTreeSet::new();
+
+        for (main_evid, incl) in evids {
+            deps.push(main_evid);
+
+            while let Some(evid) = deps.pop() {
+                if tt.contains(&evid) {
+                    // nothing to do
+                    continue;
+                }
+
+                let evwd = self
+                    .get_deps(&evid)?
+                    .ok_or(GraphError::DependencyNotFound(evid))?;
+                let mut necessary_deps = evwd.keys().filter(|&h| !tt.contains(h));
+                if let Some(&x) = necessary_deps.next() {
+                    if pending_set.contains(&evid) {
+                        // once all dependencies of a pending event are satisfied,
+                        // it won't have any necessary deps left, so we came
+                        // back to it through its own dependencies.
+                        let pos = pending.iter().position(|&h| h == evid).unwrap();
+                        let mut circuit = pending.split_off(pos);
+                        circuit.push(evid);
+                        return Err(GraphError::DependencyCircuit(circuit));
+                    }
+                    pending.push(evid);
+                    pending_set.insert(evid);
+                    deps.push(evid);
+                    deps.push(x);
+                    deps.extend(necessary_deps.copied());
+                } else {
+                    if pending.last() == Some(&evid) {
+                        pending.pop();
+                        pending_set.remove(&evid);
+                    }
+                    if evid == main_evid && incl != IncludeSpec::IncludeAll {
+                        // we want to omit the final dep
+                        deps.clear();
+                        break;
+                    }
+                    // run the item, all dependencies are satisfied
+                    ret.push(evid);
+                    tt.insert(evid);
+                }
+            }
+        }
+        Ok(ret)
+    }
+}
+
+impl<Arg> GraphStore<Arg> for Graph<Arg> {
+    type EventRef<'a>
+        = &'a Event<Arg>
+    where
+        Arg: 'a;
+
+    fn hash_algo(&self) -> HashAlgo {
+        self.hash_algo
+    }
+
+    fn get_event(&self, evid: &Hash) -> Result<Option<&Event<Arg>>, GraphError> {
+        Ok(self.events.get(evid))
+    }
+
+    fn get_deps(&self, evid: &Hash) -> Result<Option<Cow<'_, BTreeMap<Hash, bool>>>, GraphError> {
+        Ok(self.events.get(evid).map(|ev| Cow::Borrowed(&ev.deps)))
+    }
+
+    fn insert_event(&mut self, evid: Hash, ev: Event<Arg>) -> Result<(), GraphError> {
+        self.insert_indexed(evid, ev);
+        Ok(())
+    }
+
+    fn nstate_names(&self) -> Result<Vec<String>, GraphError> {
+        Ok(self.nstates.keys().cloned().collect())
+    }
+
+    fn get_nstate(&self, name: &str) -> Result<Option<BTreeSet<Hash>>, GraphError> {
+        Ok(self.nstates.get(name).cloned())
+    }
+
+    fn set_nstate(&mut self, name: &str, st: BTreeSet<Hash>) -> Result<(), GraphError> {
+        self.nstates.insert(name.to_string(), st);
+        Ok(())
+    }
+
+    fn remove_nstate(&mut self, name: &str) -> Result<Option<BTreeSet<Hash>>, GraphError> {
+        Ok(self.nstates.remove(name))
+    }
+
+    fn get_meta(&self, evid: &Hash) -> Result<Option<EventMeta>, GraphError> {
+        Ok(self.meta.get(evid).cloned())
+    }
+
+    fn merge_meta(&mut self, evid: Hash, meta: EventMeta) -> Result<(), GraphError> {
+        Graph::merge_meta(self, evid, meta)
+    }
+
+    fn get_signatures(&self, subject: &Hash) -> Result<Signatures, GraphError> {
+        Ok(self.signatures.get(subject).cloned().unwrap_or_default())
+    }
+
+    fn add_signature(
+        &mut self,
+        subject: Hash,
+        signer: SignerId,
+        sig: Signature,
+    ) -> Result<(), GraphError> {
+        Graph::add_signature(self, subject, signer, sig);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use proptest::sample::Index;
+
+    /// the original fixpoint implementation of `fold_state`
+    fn fold_state_naive(
+        g: &Graph<String>,
+        mut st: BTreeMap<Hash, bool>,
+        expand: bool,
+    ) -> Result<BTreeMap<Hash, bool>, GraphError> {
+        loop {
+            let orig_len = st.len();
+            for (h, _) in st.clone() {
+                match g.get_deps(&h)? {
+                    Some(x) => st.extend(x.keys().map(|&j| (j, true))),
+                    None => {
+                        return Err(GraphError::DependencyNotFound(h));
+                    }
+                }
+            }
+            if orig_len == st.len() {
+                break;
+            }
+        }
+        if !expand {
+            st.retain(|_, is_dep| !*is_dep);
+        }
+        Ok(st)
+    }
+
+    /// events with random dependencies on earlier events
+    fn arb_graph() -> impl Strategy<Value = (Graph<String>, Vec<Hash>)> {
+        prop::collection::vec(
+            prop::collection::vec((any::<Index>(), any::<bool>()), 0..4),
+            1..40,
+        )
+        .prop_map(|evs| {
+            let mut g = Graph::default();
+            let mut hs: Vec<Hash> = Vec::new();
+            for (i, deps) in evs.into_iter().enumerate() {
+                let deps = if hs.is_empty() {
+                    BTreeMap::new()
+                } else {
+                    deps.into_iter()
+                        .map(|(idx, is_hard)| (*idx.get(&hs), is_hard))
+                        .collect()
+                };
+                let ev = Event {
+                    cmd: i as u32,
+                    arg: String::new(),
+                    deps,
+                };
+                hs.push(g.ensure_event(ev).1);
+            }
+            (g, hs)
+        })
+    }
+
+    fn seeds(hs: &[Hash], sel: &[(Index, bool)]) -> BTreeMap<Hash, bool> {
+        sel.iter().map(|(idx, x)| (*idx.get(hs), *x)).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn fold_state_matches_naive(
+            (g, hs) in arb_graph(),
+            sel in prop::collection::vec((any::<Index>(), any::<bool>()), 0..8),
+            expand in any::<bool>(),
+        ) {
+            let st = seeds(&hs, &sel);
+            prop_assert_eq!(
+                g.fold_state(st.clone(), expand).unwrap(),
+                fold_state_naive(&g, st, expand).unwrap()
+            );
+        }
+
+        #[test]
+        fn fold_state_cached_matches_naive(
+            (g, hs) in arb_graph(),
+            queries in prop::collection::vec(
+                (
+                    any::<bool>(),
+                    prop::collection::vec((any::<Index>(), any::<bool>()), 0..4),
+                    any::<bool>(),
+                ),
+                1..6,
+            ),
+        ) {
+            let mut cache = ClosureCache::new();
+            let mut heads = BTreeMap::new();
+            for (extend, sel, expand) in queries {
+                // either grow the previous state (hitting the cache) or start over
+                let mut st = if extend { heads.clone() } else { BTreeMap::new() };
+                st.extend(seeds(&hs, &sel));
+                let cached = g.fold_state_cached(st.clone(), expand, &mut cache).unwrap();
+                prop_assert_eq!(&cached, &fold_state_naive(&g, st.clone(), expand).unwrap());
+                heads = g.fold_state(st, false).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn fold_state_missing() {
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(Event {
+            cmd: 0,
+            arg: String::new(),
+            deps: BTreeMap::new(),
+        });
+        let missing = HashAlgo::default().calculate(b"missing");
+        let st: BTreeMap<_, _> = [(a, false), (missing, false)].into_iter().collect();
+        let mut cache = ClosureCache::new();
+        assert!(matches!(
+            g.fold_state_cached(st, false, &mut cache),
+            Err(GraphError::DependencyNotFound(h)) if h == missing
+        ));
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,487 @@
+use crate::{
+    container::arg_type_name, Compression, ContainerError, Event, EventMeta, Graph, GraphError,
+    GraphStore, Hash, Header, IncludeSpec, Signatures,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+
+/// the current sync protocol version, both sides need to use the same
+pub const SYNC_VERSION: u16 = 1;
+
+/// the maximum size of a received message (e.g. a single event),
+/// larger ones abort the sync
+pub const SYNC_MESSAGE_LIMIT: u64 = 16 << 20;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SyncError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("(de)serialization error: {0}")]
+    Bincode(#[from] bincode::Error),
+
+    #[error(transparent)]
+    Graph(#[from] GraphError),
+
+    /// the engine or argument type of the remote doesn't match
+    #[error(transparent)]
+    Container(#[from] ContainerError),
+
+    #[error("unsupported sync protocol version {0} (this build supports {SYNC_VERSION})")]
+    UnsupportedVersion(u16),
+
+    #[error("hash algorithm mismatch: remote uses {got:?}, expected {expected:?}")]
+    HashAlgoMismatch {
+        expected: crate::HashAlgo,
+        got: crate::HashAlgo,
+    },
+
+    #[error("remote doesn't have a state named '{0}'")]
+    StateNotFound(String),
+
+    #[error("received event doesn't match its hash {0}")]
+    HashMismatch(Hash),
+
+    #[error("hash collision @ {0} detected during insertion of received event")]
+    Collision(Hash),
+
+    #[error("protocol violation: expected {expected}, got {got}")]
+    Unexpected {
+        expected: &'static str,
+        got: &'static str,
+    },
+}
+
+/// A message of the sync protocol.
+///
+/// Both sides start by sending [`Message::Hello`]. The server then announces
+/// its named states, the client replies with the heads it wants and
+/// negotiates the events both sides have by sending batches of events it
+/// has, starting with its heads, until it reaches events known to the
+/// server (or runs out of events). Afterwards, the server sends the missing
+/// events in dependency order.
+// NOTE: the order of the variants is part of the protocol
+#[derive(Deserialize, Serialize)]
+enum Message<E> {
+    Hello {
+        version: u16,
+        header: Header,
+    },
+    /// server: its named states
+    States(BTreeMap<String, BTreeSet<Hash>>),
+    /// client: the events it wants (including their dependencies)
+    Want(BTreeSet<Hash>),
+    /// client: events it has
+    Have(BTreeSet<Hash>),
+    /// server: the events of the last `Have` which it has, too
+    Ack(BTreeSet<Hash>),
+    /// client: negotiation is finished
+    Done,
+    /// server: a missing event
+    Event {
+        evid: Hash,
+        event: E,
+        meta: Option<EventMeta>,
+        signatures: Signatures,
+    },
+    /// server: all missing events were sent
+    End,
+}
+
+impl<E> Message<E> {
+    fn kind(&self) -> &'static str {
+        match self {
+            Message::Hello { .. } => "hello",
+            Message::States(_) => "states",
+            Message::Want(_) => "want",
+            Message::Have(_) => "have",
+            Message::Ack(_) => "ack",
+            Message::Done => "done",
+            Message::Event { .. } => "event",
+            Message::End => "end",
+        }
+    }
+
+    fn unexpected<T>(self, expected: &'static str) -> Result<T, SyncError> {
+        Err(SyncError::Unexpected {
+            expected,
+            got: self.kind(),
+        })
+    }
+}
+
+fn send<E: Serialize, W: Write>(w: &mut W, msg: &Message<E>) -> Result<(), SyncError> {
+    bincode::serialize_into(w, msg)?;
+    Ok(())
+}
+
+fn recv<E: DeserializeOwned, R: Read>(r: &mut R) -> Result<Message<E>, SyncError> {
+    use bincode::Options;
+    // same encoding as `bincode::deserialize_from`, but the remote
+    // shouldn't be able to make us allocate arbitrary amounts of memory
+    Ok(bincode::options()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(SYNC_MESSAGE_LIMIT)
+        .deserialize_from(r)?)
+}
+
+/// The result of [`Graph::fetch`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fetched {
+    /// the heads of the fetched state on the remote side
+    pub heads: BTreeSet<Hash>,
+    /// the events which were missing, in dependency order
+    pub events: Vec<Hash>,
+}
+
+impl<Arg> Graph<Arg> {
+    fn hello<E, W: Write>(&self, w: &mut W, engine: &str) -> Result<(), SyncError>
+    where
+        E: Serialize,
+    {
+        send::<E, _>(
+            w,
+            &Message::Hello {
+                version: SYNC_VERSION,
+                header: Header {
+                    engine: engine.to_string(),
+                    arg_type: arg_type_name::<Arg>(),
+                    hash_algo: self.hash_algo,
+                    compression: Compression::None,
+                },
+            },
+        )?;
+        w.flush()?;
+        Ok(())
+    }
+
+    fn check_hello<E, R: Read>(&self, r: &mut R, engine: &str) -> Result<(), SyncError>
+    where
+        E: DeserializeOwned,
+    {
+        match recv::<E, _>(r)? {
+            Message::Hello { version, header } => {
+                if version != SYNC_VERSION {
+                    return Err(SyncError::UnsupportedVersion(version));
+                }
+                header.check(engine)?;
+                if header.hash_algo != self.hash_algo {
+                    return Err(SyncError::HashAlgoMismatch {
+                        expected: self.hash_algo,
+                        got: header.hash_algo,
+                    });
+                }
+                Ok(())
+            }
+            msg => msg.unexpected("hello"),
+        }
+    }
+
+    /// serve a single [`Graph::fetch`] request of the other side of the
+    /// connection (`r`, `w`), which needs to use the same `engine`.
+    pub fn serve<R: Read, W: Write>(&self, r: R, w: W, engine: &str) -> Result<(), SyncError>
+    where
+        Arg: Serialize,
+    {
+        let (mut r, mut w) = (BufReader::new(r), BufWriter::new(w));
+        self.hello::<&Event<Arg>, _>(&mut w, engine)?;
+        self.check_hello::<(), _>(&mut r, engine)?;
+        send::<(), _>(&mut w, &Message::States(self.nstates.clone()))?;
+        w.flush()?;
+
+        let wants = match recv::<(), _>(&mut r)? {
+            Message::Want(wants) => wants,
+            msg => return msg.unexpected("want"),
+        };
+        if let Some(h) = wants.iter().find(|h| !self.events.contains_key(h)) {
+            return Err(GraphError::DependencyNotFound(*h).into());
+        }
+
+        let mut common = BTreeSet::new();
+        loop {
+            match recv::<(), _>(&mut r)? {
+                Message::Have(hs) => {
+                    let known: BTreeSet<_> = hs
+                        .into_iter()
+                        .filter(|h| self.events.contains_key(h))
+                        .collect();
+                    common.extend(known.iter().copied());
+                    send::<(), _>(&mut w, &Message::Ack(known))?;
+                    w.flush()?;
+                }
+                Message::Done => break,
+                msg => return msg.unexpected("have or done"),
+            }
+        }
+
+        let missing = self.calculate_dependencies(
+            self.ancestors(&common)?,
+            wants
+                .into_iter()
+                .map(|h| (h, IncludeSpec::IncludeAll))
+                .collect(),
+        )?;
+        for evid in missing {
+            send(
+                &mut w,
+                &Message::Event {
+                    evid,
+                    event: &self.events[&evid],
+                    meta: self.meta.get(&evid).cloned(),
+                    signatures: self.signatures.get(&evid).cloned().unwrap_or_default(),
+                },
+            )?;
+        }
+        send::<(), _>(&mut w, &Message::End)?;
+        w.flush()?;
+        Ok(())
+    }
+
+    /// fetch the state `name` from the other side of the connection (`r`, `w`),
+    /// which calls [`Graph::serve`]. only the events which are missing locally
+    /// are transferred, together with their metadata and signatures.
+    /// the fetched state isn't applied to any local state.
+    ///
+    /// received events are only inserted (via [`Graph::ensure_event`]) once
+    /// all of them were received and checked, a failed fetch leaves the graph
+    /// untouched.
+    pub fn fetch<R: Read, W: Write>(
+        &mut self,
+        r: R,
+        w: W,
+        engine: &str,
+        name: &str,
+    ) -> Result<Fetched, SyncError>
+    where
+        Arg: esvc_traits::CommandArg + DeserializeOwned,
+    {
+        let (mut r, mut w) = (BufReader::new(r), BufWriter::new(w));
+        self.hello::<(), _>(&mut w, engine)?;
+        self.check_hello::<Event<Arg>, _>(&mut r, engine)?;
+        let heads = match recv::<(), _>(&mut r)? {
+            Message::States(mut nstates) => nstates
+                .remove(name)
+                .ok_or_else(|| SyncError::StateNotFound(name.to_string()))?,
+            msg => return msg.unexpected("states"),
+        };
+
+        let wants: BTreeSet<_> = heads
+            .iter()
+            .filter(|h| !self.events.contains_key(h))
+            .copied()
+            .collect();
+        send::<(), _>(&mut w, &Message::Want(wants.clone()))?;
+
+        if !wants.is_empty() {
+            // start with the local heads, and walk along the dependencies
+            // of the events the server doesn't know
+            let mut frontier: BTreeSet<_> = self
+                .events
+                .keys()
+                .filter(|h| self.dependents(h).is_empty())
+                .copied()
+                .collect();
+            let mut visited = BTreeSet::new();
+            while !frontier.is_empty() {
+                send::<(), _>(&mut w, &Message::Have(frontier.clone()))?;
+                w.flush()?;
+                let known = match recv::<(), _>(&mut r)? {
+                    Message::Ack(known) => known,
+                    msg => return msg.unexpected("ack"),
+                };
+                visited.extend(frontier.iter().copied());
+                frontier = frontier
+                    .difference(&known)
+                    .flat_map(|h| self.events[h].deps.keys())
+                    .filter(|h| !visited.contains(h))
+                    .copied()
+                    .collect();
+            }
+        }
+        send::<(), _>(&mut w, &Message::Done)?;
+        w.flush()?;
+
+        let mut received = Vec::new();
+        let mut available = BTreeSet::new();
+        loop {
+            match recv::<Event<Arg>, _>(&mut r)? {
+                Message::Event {
+                    evid,
+                    event,
+                    meta,
+                    signatures,
+                } => {
+                    if let Some(h) = event
+                        .deps
+                        .keys()
+                        .find(|&h| !available.contains(h) && !self.events.contains_key(h))
+                    {
+                        return Err(GraphError::DependencyNotFound(*h).into());
+                    }
+                    let serval = bincode::serialize(&event).unwrap();
+                    if self.hash_algo.calculate(&serval[..]) != evid {
+                        return Err(SyncError::HashMismatch(evid));
+                    }
+                    if matches!(self.events.get(&evid), Some(other) if other != &event) {
+                        return Err(SyncError::Collision(evid));
+                    }
+                    available.insert(evid);
+                    received.push((evid, event, meta, signatures));
+                }
+                Message::End => break,
+                msg => return msg.unexpected("event or end"),
+            }
+        }
+        if let Some(h) = wants
+            .iter()
+            .find(|h| !available.contains(h) && !self.events.contains_key(h))
+        {
+            return Err(GraphError::DependencyNotFound(*h).into());
+        }
+
+        let mut events = Vec::with_capacity(received.len());
+        for (evid, event, meta, signatures) in received {
+            self.ensure_event(event);
+            if let Some(meta) = meta {
+                self.merge_meta(evid, meta)?;
+            }
+            for (signer, sig) in signatures {
+                self.add_signature(evid, signer, sig);
+            }
+            events.push(evid);
+        }
+        Ok(Fetched { heads, events })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixStream;
+
+    fn ev(g: &mut Graph<String>, arg: &str, deps: &[Hash]) -> Hash {
+        g.ensure_event(Event {
+            cmd: 0,
+            arg: arg.to_string(),
+            deps: deps.iter().map(|&h| (h, true)).collect(),
+        })
+        .1
+    }
+
+    fn run_fetch(
+        server: &Graph<String>,
+        client: &mut Graph<String>,
+        engines: (&str, &str),
+        name: &str,
+    ) -> (Result<(), SyncError>, Result<Fetched, SyncError>) {
+        let (s, c) = UnixStream::pair().unwrap();
+        std::thread::scope(|scope| {
+            let srv = scope.spawn(|| {
+                let ret = server.serve(&s, &s, engines.0);
+                // unblock the client if the server failed
+                let _ = s.shutdown(std::net::Shutdown::Both);
+                ret
+            });
+            let ret = client.fetch(&c, &c, engines.1, name);
+            let _ = c.shutdown(std::net::Shutdown::Both);
+            (srv.join().unwrap(), ret)
+        })
+    }
+
+    #[test]
+    fn fetch_missing() {
+        let mut server = Graph::default();
+        let a = ev(&mut server, "a", &[]);
+        let b = ev(&mut server, "b", &[a]);
+        let c = ev(&mut server, "c", &[b]);
+        server
+            .nstates
+            .insert(String::new(), [c].into_iter().collect());
+        server
+            .merge_meta(
+                c,
+                EventMeta {
+                    message: Some("hi".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        // the client has `a`, but only announces its own event `x`
+        let mut client = Graph::default();
+        assert_eq!(ev(&mut client, "a", &[]), a);
+        let x = ev(&mut client, "x", &[a]);
+
+        let (srv, ret) = run_fetch(&server, &mut client, ("test", "test"), "");
+        srv.unwrap();
+        let fetched = ret.unwrap();
+        assert_eq!(fetched.heads, [c].into_iter().collect());
+        assert_eq!(fetched.events, vec![b, c]);
+        assert!(client.events.contains_key(&x));
+        assert_eq!(client.events[&c], server.events[&c]);
+        assert_eq!(client.meta[&c].message.as_deref(), Some("hi"));
+
+        // nothing left to fetch
+        let (srv, ret) = run_fetch(&server, &mut client, ("test", "test"), "");
+        srv.unwrap();
+        assert!(ret.unwrap().events.is_empty());
+    }
+
+    #[test]
+    fn fetch_errors() {
+        let mut server = Graph::default();
+        let a = ev(&mut server, "a", &[]);
+        server
+            .nstates
+            .insert(String::new(), [a].into_iter().collect());
+        let mut client = Graph::default();
+
+        let (_, ret) = run_fetch(&server, &mut client, ("test", "test"), "nope");
+        assert!(matches!(ret, Err(SyncError::StateNotFound(_))));
+
+        let (srv, ret) = run_fetch(&server, &mut client, ("test", "other"), "");
+        assert!(matches!(
+            srv,
+            Err(SyncError::Container(ContainerError::EngineMismatch { .. }))
+        ));
+        assert!(matches!(
+            ret,
+            Err(SyncError::Container(ContainerError::EngineMismatch { .. }))
+        ));
+        assert!(client.events.is_empty());
+
+        // a tampered event aborts the fetch, and nothing gets inserted
+        let fake = crate::HashAlgo::default().calculate(b"fake");
+        let b = ev(&mut server, "b", &[a]);
+        let tampered = server.events.remove(&b).unwrap();
+        server.events.insert(fake, tampered);
+        server.rebuild_dependents();
+        server
+            .nstates
+            .insert(String::new(), [fake].into_iter().collect());
+        let (_, ret) = run_fetch(&server, &mut client, ("test", "test"), "");
+        assert!(matches!(ret, Err(SyncError::HashMismatch(h)) if h == fake));
+        assert!(client.events.is_empty());
+    }
+
+    #[test]
+    fn message_limit() {
+        // `States` with a single name, which claims to be 1 TiB large
+        let mut msg = 1u32.to_le_bytes().to_vec();
+        msg.extend_from_slice(&1u64.to_le_bytes());
+        msg.extend_from_slice(&(1u64 << 40).to_le_bytes());
+        match recv::<(), _>(&mut &msg[..]) {
+            Err(SyncError::Bincode(e)) => {
+                assert!(matches!(*e, bincode::ErrorKind::SizeLimit), "{:?}", e)
+            }
+            _ => panic!("oversized message got accepted"),
+        }
+
+        // regular messages still use the same encoding as `send`
+        let mut buf = Vec::new();
+        send::<(), _>(&mut buf, &Message::Done).unwrap();
+        assert!(matches!(recv::<(), _>(&mut &buf[..]), Ok(Message::Done)));
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{graph::fmt_path, Graph, Hash};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A single inconsistency found by [`Graph::verify`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum Violation {
+    #[error("event stored as {key} actually hashes to {actual}")]
+    HashMismatch { key: Hash, actual: Hash },
+
+    #[error("event {event} depends on missing event {dep}")]
+    DanglingDep { event: Hash, dep: Hash },
+
+    /// the path starts and ends with the same event,
+    /// each entry depends on the next one
+    #[error("dependency cycle {}", fmt_path(.0))]
+    Cycle(Vec<Hash>),
+
+    #[error("state '{state}' references missing event {member}")]
+    DanglingStateMember { state: String, member: Hash },
+}
+
+impl<Arg: Serialize> Graph<Arg> {
+    /// check the integrity of the graph, e.g. after loading it from disk.
+    /// this should be done before handing the graph to a `WorkCache`,
+    /// because that assumes that the graph is consistent.
+    ///
+    /// @returns all violations found, an empty list means that the graph is fine
+    pub fn verify(&self) -> Vec<Violation> {
+        let mut ret = Vec::new();
+
+        for (&key, ev) in &self.events {
+            let serval = bincode::serialize(ev).unwrap();
+            let actual = key.algo().calculate(&serval[..]);
+            if actual != key {
+                ret.push(Violation::HashMismatch { key, actual });
+            }
+            ret.extend(
+                ev.deps
+                    .keys()
+                    .filter(|dep| !self.events.contains_key(dep))
+                    .map(|&dep| Violation::DanglingDep { event: key, dep }),
+            );
+        }
+
+        ret.extend(self.find_cycles().into_iter().map(Violation::Cycle));
+
+        for (state, members) in &self.nstates {
+            ret.extend(
+                members
+                    .iter()
+                    .filter(|h| !self.events.contains_key(h))
+                    .map(|&member| Violation::DanglingStateMember {
+                        state: state.clone(),
+                        member,
+                    }),
+            );
+        }
+
+        ret
+    }
+
+    /// depth-first search for back edges, dangling deps are ignored here
+    fn find_cycles(&self) -> Vec<Vec<Hash>> {
+        let mut ret = Vec::new();
+        // evid -> is_finished
+        let mut visited = BTreeMap::<Hash, bool>::new();
+
+        for (&root, root_ev) in &self.events {
+            if visited.contains_key(&root) {
+                continue;
+            }
+            visited.insert(root, false);
+            let mut path = vec![root];
+            let mut pending = vec![root_ev.deps.keys()];
+
+            while let Some(it) = pending.last_mut() {
+                if let Some(&dep) = it.next() {
+                    match visited.get(&dep) {
+                        None => {
+                            if let Some(dep_ev) = self.events.get(&dep) {
+                                visited.insert(dep, false);
+                                path.push(dep);
+                                pending.push(dep_ev.deps.keys());
+                            }
+                        }
+                        Some(false) => {
+                            let pos = path.iter().position(|&h| h == dep).unwrap();
+                            let mut cycle = path[pos..].to_vec();
+                            cycle.push(dep);
+                            ret.push(cycle);
+                        }
+                        Some(true) => {}
+                    }
+                } else {
+                    pending.pop();
+                    visited.insert(path.pop().unwrap(), true);
+                }
+            }
+        }
+
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Event;
+
+    fn ev(arg: &str, deps: &[Hash]) -> Event<String> {
+        Event {
+            cmd: 0,
+            arg: arg.to_string(),
+            deps: deps.iter().map(|&h| (h, true)).collect(),
+        }
+    }
+
+    #[test]
+    fn verify_ok() {
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(ev("a", &[]));
+        let (_, b) = g.ensure_event(ev("b", &[a]));
+        g.nstates.insert(String::new(), [b].into_iter().collect());
+        assert_eq!(g.verify(), Vec::new());
+    }
+
+    #[test]
+    fn verify_violations() {
+        let mut g = Graph::default();
+        let (_, a) = g.ensure_event(ev("a", &[]));
+        let (_, b) = g.ensure_event(ev("b", &[]));
+        let (_, missing) = Graph::default().ensure_event(ev("missing", &[]));
+
+        // tamper with the graph
+        g.events.get_mut(&a).unwrap().deps.insert(b, true);
+        g.events.get_mut(&b).unwrap().deps.insert(a, false);
+        g.events.get_mut(&b).unwrap().deps.insert(missing, true);
+        g.nstates
+            .insert("x".to_string(), [a, missing].into_iter().collect());
+
+        let vs = g.verify();
+        assert_eq!(vs.len(), 5, "{:?}", vs);
+        assert!(vs
+            .iter()
+            .any(|v| matches!(v, Violation::HashMismatch { key, .. } if *key == a)));
+        assert!(vs
+            .iter()
+            .any(|v| matches!(v, Violation::HashMismatch { key, .. } if *key == b)));
+        assert!(vs.contains(&Violation::DanglingDep {
+            event: b,
+            dep: missing
+        }));
+        assert!(vs.contains(&Violation::DanglingStateMember {
+            state: "x".to_string(),
+            member: missing
+        }));
+        let cycle = vs
+            .iter()
+            .find_map(|v| match v {
+                Violation::Cycle(c) => Some(c),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(cycle.first(), cycle.last());
+    }
+}
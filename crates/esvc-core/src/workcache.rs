@@ -1,4 +1,7 @@
-use crate::{Event, Graph, GraphError, Hash, IncludeSpec};
+// `WorkCacheError::HashChangeAtMerge` carries two hashes, which makes it rather large
+#![allow(clippy::result_large_err)]
+
+use crate::{Event, EventMeta, GraphError, GraphStore, Hash, IncludeSpec, SignatureStatus};
 use core::fmt;
 use esvc_traits::Engine;
 use std::collections::{BTreeMap, BTreeSet};
@@ -50,6 +53,10 @@ pub enum WorkCacheError<EE> {
     #[error("event {0} got turned into a no-op at merge")]
     NoopAtMerge(Hash),
 
+    /// the event was rejected by the merge policy
+    #[error("refusing to merge event {0} ({1:?})")]
+    Untrusted(Hash, SignatureStatus),
+
     #[error(transparent)]
     Engine(EE),
 }
@@ -65,9 +72,9 @@ impl<'a, En: Engine> WorkCache<'a, En> {
     }
 
     /// invariant: `deps` and `tt` are distinct
-    fn run_deps(
+    fn run_deps<G: GraphStore<En::Arg>>(
         &mut self,
-        graph: &Graph<En::Arg>,
+        graph: &G,
         mut tt: BTreeSet<Hash>,
         deps: Vec<Hash>,
     ) -> RunResult<'_, En> {
@@ -75,8 +82,7 @@ impl<'a, En: Engine> WorkCache<'a, En> {
 
         for &evid in &deps {
             let evwd = graph
-                .events
-                .get(&evid)
+                .get_event(&evid)?
                 .ok_or(GraphError::DependencyNotFound(evid))?;
 
             // run the item, all dependencies are satisfied
@@ -107,9 +113,9 @@ impl<'a, En: Engine> WorkCache<'a, En> {
         Ok((res, tt))
     }
 
-    pub fn run_foreach_recursively(
+    pub fn run_foreach_recursively<G: GraphStore<En::Arg>>(
         &mut self,
-        graph: &Graph<En::Arg>,
+        graph: &G,
         evids: BTreeMap<Hash, IncludeSpec>,
     ) -> RunResult<'_, En> {
         let deps = graph.calculate_dependencies(Default::default(), evids)?;
@@ -117,10 +123,10 @@ impl<'a, En: Engine> WorkCache<'a, En> {
     }
 
     /// NOTE: this ignores the contents of `ev.deps`
-    #[cfg_attr(feature = "tracing", tracing::instrument(skip(seed_deps)))]
-    pub fn shelve_event(
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(graph, seed_deps)))]
+    pub fn shelve_event<G: GraphStore<En::Arg>>(
         &mut self,
-        graph: &mut Graph<En::Arg>,
+        graph: &mut G,
         mut seed_deps: BTreeSet<Hash>,
         mut ev: Event<En::Arg>,
     ) -> Result<Option<Hash>, WorkCacheError<En::Error>> {
@@ -170,10 +176,7 @@ impl<'a, En: Engine> WorkCache<'a, En> {
             #[cfg(feature = "tracing")]
             let _enter = trc_span.enter();
 
-            seed_deps = seed_deps
-                .into_iter()
-                .filter(|conc_evid| !cur_deps.contains_key(conc_evid))
-                .collect();
+            seed_deps.retain(|conc_evid| !cur_deps.contains_key(conc_evid));
 
             // calculate cur state
             let (base_st, _base_tt) = self.run_foreach_recursively(
@@ -270,7 +273,9 @@ impl<'a, En: Engine> WorkCache<'a, En> {
 
             for (conc_evid, tmptt) in seed_deps2 {
                 let base_st = self.sts.get(&tmptt).unwrap();
-                let conc_ev = graph.events.get(&conc_evid).unwrap();
+                let conc_ev = graph
+                    .get_event(&conc_evid)?
+                    .ok_or(GraphError::DependencyNotFound(conc_evid))?;
                 #[allow(clippy::if_same_then_else, clippy::let_and_return)]
                 let is_indep = if &cur_st == base_st {
                     // this is a revert
@@ -360,7 +365,9 @@ impl<'a, En: Engine> WorkCache<'a, En> {
                 .map_err(WorkCacheError::Engine)?;
             seed_deps = seed_deps.difference(&bare_tt).copied().collect();
             for &conc_evid in &seed_deps {
-                let conc_ev = graph.events.get(&conc_evid).unwrap();
+                let conc_ev = graph
+                    .get_event(&conc_evid)?
+                    .ok_or(GraphError::DependencyNotFound(conc_evid))?;
                 tmp_st = engine
                     .run_event_bare(conc_ev.cmd, &conc_ev.arg, &tmp_st)
                     .map_err(WorkCacheError::Engine)?;
@@ -408,7 +415,7 @@ impl<'a, En: Engine> WorkCache<'a, En> {
         };
 
         // register event
-        let (collinfo, evhash) = graph.ensure_event(ev);
+        let (collinfo, evhash) = graph.ensure_event(ev)?;
         if let Some(ev) = collinfo {
             return Err(GraphError::HashCollision(evhash, format!("{:?}", ev)).into());
         }
@@ -416,9 +423,25 @@ impl<'a, En: Engine> WorkCache<'a, En> {
         Ok(Some(evhash))
     }
 
-    pub fn try_merge(
+    /// like [`WorkCache::shelve_event`], but also merges `meta` into
+    /// the metadata of the resulting event
+    pub fn shelve_event_with_meta<G: GraphStore<En::Arg>>(
         &mut self,
-        graph: &mut Graph<En::Arg>,
+        graph: &mut G,
+        seed_deps: BTreeSet<Hash>,
+        ev: Event<En::Arg>,
+        meta: EventMeta,
+    ) -> Result<Option<Hash>, WorkCacheError<En::Error>> {
+        let ret = self.shelve_event(graph, seed_deps, ev)?;
+        if let Some(h) = ret {
+            graph.merge_meta(h, meta)?;
+        }
+        Ok(ret)
+    }
+
+    pub fn try_merge<G: GraphStore<En::Arg>>(
+        &mut self,
+        graph: &mut G,
         sts: BTreeSet<Hash>,
     ) -> Result<(), WorkCacheError<En::Error>>
     where
@@ -438,8 +461,7 @@ impl<'a, En: Engine> WorkCache<'a, En> {
 
         let mut seed_deps: BTreeSet<_> = graph
             .fold_state(full_seed_deps.iter().map(|&h| (h, false)).collect(), false)?
-            .into_iter()
-            .map(|(h, _)| h)
+            .into_keys()
             .collect();
 
         #[cfg(feature = "tracing")]
@@ -449,11 +471,11 @@ impl<'a, En: Engine> WorkCache<'a, En> {
             if full_seed_deps.contains(&i) {
                 continue;
             }
-            let ev = graph.events[&i].clone();
+            let ev = get_event_cloned(graph, i)?;
             if let Some(ih) = self.shelve_event(graph, seed_deps.clone(), ev)? {
                 if ih != i {
-                    let ev = graph.events[&i].clone();
-                    let nev = graph.events[&ih].clone();
+                    let ev = get_event_cloned(graph, i)?;
+                    let nev = get_event_cloned(graph, ih)?;
                     if nev
                         .deps
                         .iter()
@@ -479,11 +501,43 @@ impl<'a, En: Engine> WorkCache<'a, En> {
     }
 }
 
+#[cfg(feature = "signatures")]
+impl<En: Engine> WorkCache<'_, En> {
+    /// like [`WorkCache::try_merge`], but refuses to merge if any event
+    /// in the closure of `sts` which isn't part of `policy.local`
+    /// isn't signed by a trusted key
+    pub fn try_merge_with_policy<G: GraphStore<En::Arg>>(
+        &mut self,
+        graph: &mut G,
+        sts: BTreeSet<Hash>,
+        policy: &crate::MergePolicy<'_>,
+    ) -> Result<(), WorkCacheError<En::Error>>
+    where
+        En::Arg: Clone,
+    {
+        if let Some((h, status)) = policy.check(graph, &sts)? {
+            return Err(WorkCacheError::Untrusted(h, status));
+        }
+        self.try_merge(graph, sts)
+    }
+}
+
+fn get_event_cloned<Arg: Clone, G: GraphStore<Arg>>(
+    graph: &G,
+    evid: Hash,
+) -> Result<Event<Arg>, GraphError> {
+    graph
+        .get_event(&evid)?
+        .map(|ev| (*ev).clone())
+        .ok_or(GraphError::DependencyNotFound(evid))
+}
+
 // this is somewhat equivalent to the fuzzer code,
 // and is used to test known edge cases
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Graph;
     #[derive(Clone, Debug, PartialEq, serde::Serialize)]
     struct SearEvent<'a>(&'a str, &'a str);
 
@@ -506,14 +560,14 @@ mod tests {
 
         fn run_event_bare(&self, cmd: u32, arg: &SearEvent, dat: &String) -> Result<String, ()> {
             assert_eq!(cmd, 0);
-            Ok(dat.replace(&arg.0, &arg.1))
+            Ok(dat.replace(arg.0, arg.1))
         }
     }
 
     fn assert_no_reorder_inner(start: &str, sears: Vec<SearEvent<'static>>) {
         let expected = sears
             .iter()
-            .fold(start.to_string(), |acc, item| acc.replace(&item.0, &item.1));
+            .fold(start.to_string(), |acc, item| acc.replace(item.0, item.1));
         let e = SearEngine;
         let mut g = Graph::default();
         let mut w = WorkCache::new(&e, start.to_string());
@@ -796,4 +850,46 @@ mod tests {
             );
         });
     }
+
+    #[cfg(feature = "signatures")]
+    #[test]
+    fn merge_policy() {
+        let alice = ed25519_dalek::SigningKey::from_bytes(&[1; 32]);
+        let mut keyring = crate::Keyring::default();
+        keyring.insert((&alice.verifying_key()).into(), "alice".to_string());
+
+        let e = SearEngine;
+        let mut g = Graph::default();
+        let mut w = WorkCache::new(&e, "ab".to_string());
+        let local = w
+            .shelve_event(&mut g, Default::default(), SearEvent("a", "x").into())
+            .unwrap()
+            .unwrap();
+        let foreign = w
+            .shelve_event(&mut g, Default::default(), SearEvent("b", "y").into())
+            .unwrap()
+            .unwrap();
+
+        let sts: BTreeSet<_> = [local, foreign].into_iter().collect();
+        let policy = crate::MergePolicy {
+            keyring: &keyring,
+            local: [local].into_iter().collect(),
+        };
+        match w.try_merge_with_policy(&mut g, sts.clone(), &policy) {
+            Err(WorkCacheError::Untrusted(h, SignatureStatus::Unsigned)) => {
+                assert_eq!(h, foreign)
+            }
+            x => panic!("expected rejection, got {:?}", x),
+        }
+        g.sign_event(&alice, foreign).unwrap();
+        w.try_merge_with_policy(&mut g, sts.clone(), &policy)
+            .unwrap();
+        let (res, _) = w
+            .run_foreach_recursively(
+                &g,
+                sts.iter().map(|&h| (h, IncludeSpec::IncludeAll)).collect(),
+            )
+            .unwrap();
+        assert_eq!(res, "xy");
+    }
 }
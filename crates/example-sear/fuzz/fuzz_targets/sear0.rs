@@ -1,5 +1,6 @@
 #![no_main]
 use arbitrary::{Arbitrary, Unstructured};
+use esvc_core::GraphStore;
 use libfuzzer_sys::fuzz_target;
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -1,3 +1,4 @@
+use esvc_core::GraphStore;
 use std::collections::BTreeSet;
 use std::str::from_utf8;
 
@@ -73,7 +74,7 @@ fn main() {
     }
 
     println!(":: e.graph as .dot ::");
-    println!("{:?}", esvc_core::Dot(&g));
+    println!("{:?}", esvc_core::Dot::new(&g));
 
     println!(":: minx ::");
     let minx: BTreeSet<_> = g
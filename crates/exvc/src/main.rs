@@ -1,6 +1,9 @@
 use ansi_term::Colour;
-use esvc_core::{Graph, WorkCache};
-use std::io::Write;
+use esvc_core::{
+    ed25519_dalek::SigningKey, rusqlite, EventLog, Graph, GraphStore, Keyring, ReflogReason,
+    SqliteStore, WorkCache,
+};
+use std::io::{Seek, Write};
 use syntect::easy::HighlightLines;
 use syntect::highlighting::{Style, ThemeSet};
 use syntect::parsing::SyntaxSet;
@@ -17,6 +20,17 @@ struct Context<'en> {
     ts: ThemeSet,
     g: Graph<Arg>,
     w: WorkCache<'en, en::ExEngine>,
+    /// set if the graph is saved incrementally
+    store: Option<Store>,
+    /// used to sign new events, see [`KEY_ENV`]
+    key: Option<SigningKey>,
+    /// if set, merged events need to be signed by one of these keys
+    keyring: Option<Keyring>,
+}
+
+enum Store {
+    Log(EventLog<std::fs::File>),
+    Sqlite(SqliteStore<Arg>),
 }
 
 fn rewrap_wce(e: esvc_core::WorkCacheError<anyhow::Error>) -> anyhow::Error {
@@ -27,44 +41,367 @@ fn rewrap_wce(e: esvc_core::WorkCacheError<anyhow::Error>) -> anyhow::Error {
         Wce::Graph(e) => Wce::<Inf>::Graph(e).into(),
         Wce::HashChangeAtMerge(a, b) => Wce::<Inf>::HashChangeAtMerge(a, b).into(),
         Wce::NoopAtMerge(h) => Wce::<Inf>::NoopAtMerge(h).into(),
+        Wce::Untrusted(h, st) => Wce::<Inf>::Untrusted(h, st).into(),
         Wce::Engine(e) => e,
     }
 }
 
+/// identifies the engine and the format of its arguments in graph containers
+const ENGINE_ID: &str = "exvc";
+
+/// graphs stored in files with this extension are kept as append-only event logs
+const EVLOG_EXT: &str = "elog";
+
+/// graphs stored in files with this extension are kept in SQLite databases
+const SQLITE_EXT: &str = "sqlite";
+
+/// environment variable containing the path of the signing key file
+const KEY_ENV: &str = "EXVC_KEY";
+
+/// environment variable containing the path of the file with trusted keys
+const KEYRING_ENV: &str = "EXVC_KEYRING";
+
+fn check_graph(g: &Graph<Arg>) -> anyhow::Result<()> {
+    let violations = g.verify();
+    if !violations.is_empty() {
+        for i in &violations {
+            eprintln!("{} {}", Colour::Red.paint("E:"), i);
+        }
+        anyhow::bail!("graph is corrupted ({} violations)", violations.len());
+    }
+    Ok(())
+}
+
+fn load_graph(path: impl AsRef<std::path::Path>) -> anyhow::Result<Graph<Arg>> {
+    use esvc_core::ContainerError as Ce;
+    let path = path.as_ref();
+    if path.extension() == Some(SQLITE_EXT.as_ref()) {
+        let conn = rusqlite::Connection::open_with_flags(
+            path,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )?;
+        let g = SqliteStore::<Arg>::open(conn, ENGINE_ID)?.load_graph()?;
+        check_graph(&g)?;
+        return Ok(g);
+    }
+    let open = || -> std::io::Result<_> { Ok(std::io::BufReader::new(std::fs::File::open(path)?)) };
+    let g = match esvc_core::read_graph::<Arg, _>(open()?, ENGINE_ID) {
+        Ok(g) => g,
+        Err(Ce::InvalidMagic) => match esvc_core::replay_log::<Arg, _>(open()?, ENGINE_ID) {
+            Ok(r) => r.graph,
+            Err(Ce::InvalidMagic) => {
+                // legacy format, without any header
+                let fz = zstd::stream::read::Decoder::new(open()?)?;
+                bincode::deserialize_from::<_, Graph<Arg>>(fz)?
+            }
+            Err(e) => return Err(e.into()),
+        },
+        Err(e) => return Err(e.into()),
+    };
+    check_graph(&g)?;
+    Ok(g)
+}
+
+fn open_log(path: &camino::Utf8Path) -> anyhow::Result<(Graph<Arg>, EventLog<std::fs::File>)> {
+    if path.exists() {
+        let f = std::io::BufReader::new(std::fs::File::open(path)?);
+        let r = match esvc_core::replay_log::<Arg, _>(f, ENGINE_ID) {
+            Ok(r) => r,
+            Err(e @ esvc_core::ContainerError::CorruptedLog(_)) => anyhow::bail!(
+                "{}: {}\nrefusing to open it, repair it or recover it from a backup",
+                path,
+                e
+            ),
+            Err(e) => return Err(e.into()),
+        };
+        check_graph(&r.graph)?;
+        if r.dropped != 0 {
+            eprintln!(
+                "{} dropped {} bytes of truncated log",
+                Colour::Yellow.paint("W:"),
+                r.dropped
+            );
+        }
+        let mut f = std::fs::OpenOptions::new().write(true).open(path)?;
+        f.set_len(r.valid_len)?;
+        f.seek(std::io::SeekFrom::End(0))?;
+        let log = EventLog::resume(f, &r.graph);
+        Ok((r.graph, log))
+    } else {
+        let g = Graph::default();
+        let f = std::fs::File::create(path)?;
+        let log = EventLog::create::<Arg>(f, ENGINE_ID, g.hash_algo)?;
+        Ok((g, log))
+    }
+}
+
+fn open_db(path: &camino::Utf8Path) -> anyhow::Result<(Graph<Arg>, SqliteStore<Arg>)> {
+    if path.exists() {
+        let db = SqliteStore::open(rusqlite::Connection::open(path)?, ENGINE_ID)?;
+        let g = db.load_graph()?;
+        check_graph(&g)?;
+        Ok((g, db))
+    } else {
+        let g = Graph::default();
+        let db = SqliteStore::create(rusqlite::Connection::open(path)?, ENGINE_ID, g.hash_algo)?;
+        Ok((g, db))
+    }
+}
+
 impl Context<'_> {
     fn fullic(&mut self, line: &str) -> anyhow::Result<bool> {
         Ok(if line == "*dot" {
-            print!("{}", esvc_core::Dot(&self.g));
+            let opts = esvc_core::ExportOptions::default()
+                .abbrev(true)
+                .edge_styles(true)
+                .state_colours(true);
+            print!("{}", esvc_core::Dot::with_options(&self.g, opts).render()?);
+            true
+        } else if line == "*log" {
+            let opts = esvc_core::ExportOptions::default().state("").abbrev(true);
+            let log = esvc_core::LogGraph::with_options(&self.g, opts);
+            print!("{}", log.render(|arg| arg.to_string())?);
             true
         } else if line == "*state" {
             for h in &self.g.nstates[""] {
-                println!("{} {}", Colour::Blue.paint(">>"), h);
+                println!("{} {}", Colour::Blue.paint(">>"), self.g.abbrev(h));
+            }
+            true
+        } else if let Some(expr) = line.strip_prefix("*sel ") {
+            let sel = match self.g.select(expr) {
+                Ok(x) => x,
+                Err(esvc_core::RevsetError::Parse(e)) => {
+                    anyhow::bail!("invalid selection:\n{}", e.render(expr))
+                }
+                Err(e) => return Err(e.into()),
+            };
+            for h in &sel {
+                println!("{} {}", Colour::Blue.paint("::"), self.g.abbrev(h));
+            }
+            true
+        } else if line == "*reflog" {
+            for e in &self.g.reflog {
+                let fmt_st = |st: &Option<std::collections::BTreeSet<esvc_core::Hash>>| match st {
+                    Some(st) => st
+                        .iter()
+                        .map(|h| self.g.abbrev(h).to_string())
+                        .collect::<Vec<_>>()
+                        .join(","),
+                    None => "-".to_string(),
+                };
+                println!(
+                    "{} {:?} {:?}: {} -> {}",
+                    Colour::Blue.paint(format!("@{}", e.seq)),
+                    e.name,
+                    e.reason,
+                    fmt_st(&e.old),
+                    fmt_st(&e.new),
+                );
+            }
+            true
+        } else if let Some(n) = line.strip_prefix("*expire ") {
+            // keep only the newest N reflog entries, so that *gc can prune the rest
+            let n = self.g.expire_reflog(n.trim().parse()?);
+            println!("expired {} reflog entries", n);
+            true
+        } else if let Some(seq) = line.strip_prefix("*restore ") {
+            let seq = seq.trim().parse()?;
+            // the session always needs a current state
+            if self
+                .g
+                .reflog
+                .iter()
+                .any(|e| e.seq == seq && e.name.is_empty() && e.new.is_none())
+            {
+                anyhow::bail!("refusing to remove the current state");
+            }
+            self.g.restore_reflog(seq)?;
+            true
+        } else if let Some(path) = line.strip_prefix("*keygen ") {
+            let key = esvc_core::generate_key_file(path.trim().as_ref())?;
+            println!("{}", esvc_core::SignerId::from(&key.verifying_key()));
+            true
+        } else if line == "*sign" {
+            let key = self
+                .key
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("no signing key set (via ${})", KEY_ENV))?;
+            let me = esvc_core::SignerId::from(&key.verifying_key());
+            let closure = self.g.ancestors(&self.g.nstates[""])?;
+            for h in closure {
+                if !self
+                    .g
+                    .signatures
+                    .get(&h)
+                    .is_some_and(|s| s.contains_key(&me))
+                {
+                    self.g.sign_event(key, h)?;
+                }
+            }
+            self.g.sign_state(key, "")?;
+            true
+        } else if line == "*verify" {
+            let keyring = self.keyring.clone().unwrap_or_default();
+            let report = self.g.verify_signatures("", &keyring)?;
+            let fmt_status = |status: &esvc_core::SignatureStatus| match status {
+                esvc_core::SignatureStatus::Good(k) => {
+                    format!("signed by {}", keyring.name_of(k).unwrap_or("?"))
+                }
+                x => format!("{:?}", x),
+            };
+            println!("state: {}", fmt_status(&report.state));
+            for (h, status) in &report.events {
+                println!(
+                    "{} {} {}",
+                    Colour::Red.paint("!!"),
+                    self.g.abbrev(h),
+                    fmt_status(status)
+                );
+            }
+            if report.is_ok() {
+                println!("{}", Colour::Green.paint("OK"));
+            }
+            true
+        } else if line == "*stats" {
+            print!("{}", self.g.stats()?);
+            true
+        } else if line == "*gc" || line == "*gc?" {
+            for h in self.g.gc(line == "*gc?")? {
+                println!("{} {}", Colour::Red.paint("--"), h);
             }
             true
         } else if line == "w" {
-            if let Some(path) = &self.path {
+            if let Some(store) = &mut self.store {
+                match store {
+                    Store::Log(log) => {
+                        log.sync(&self.g)?;
+                        log.flush()?;
+                        log.get_ref().sync_all()?;
+                    }
+                    Store::Sqlite(db) => db.sync(&self.g)?,
+                }
+                true
+            } else if let Some(path) = &self.path {
                 let f = std::fs::File::create(path)?;
-                let mut fz = zstd::stream::write::Encoder::new(f, 20)?;
-                bincode::serialize_into(&mut fz, &self.g)?;
-                fz.finish()?.sync_all()?;
+                esvc_core::write_graph(f, ENGINE_ID, esvc_core::Compression::Zstd, &self.g)?
+                    .sync_all()?;
                 true
             } else {
                 anyhow::bail!("no file path is associated with this session");
             }
+        } else if let Some(path) = line.strip_prefix("*serve ") {
+            // serve a single fetch via a unix socket
+            let path = path.trim();
+            let listener = std::os::unix::net::UnixListener::bind(path)?;
+            let ret = listener
+                .accept()
+                .map_err(anyhow::Error::from)
+                .and_then(|(conn, _)| Ok(self.g.serve(&conn, &conn, ENGINE_ID)?));
+            std::fs::remove_file(path)?;
+            ret?;
+            println!("{}", Colour::Green.paint("OK"));
+            true
+        } else if let Some(args) = line.strip_prefix("*bundle ") {
+            // the optional second argument names a state the receiver already has
+            let mut args = args.split_whitespace();
+            let path = args
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("no bundle path given"))?;
+            let base = match args.next() {
+                Some(name) => Some(
+                    self.g
+                        .nstates
+                        .get(name)
+                        .ok_or_else(|| anyhow::anyhow!("unknown state {:?}", name))?,
+                ),
+                None => None,
+            };
+            let bundle = self.g.bundle("", base)?;
+            let f = std::fs::File::create(path)?;
+            esvc_core::write_bundle(
+                f,
+                ENGINE_ID,
+                self.g.hash_algo,
+                esvc_core::Compression::Zstd,
+                &bundle,
+            )?
+            .sync_all()?;
+            println!("bundled {} events", bundle.events.len());
+            true
+        } else if line == "b<" {
+            let mut line = String::new();
+            let stdin = std::io::stdin();
+            stdin.read_line(&mut line)?;
+            line.truncate(line.trim_end_matches(&['\r', '\n'][..]).len());
+
+            let f = std::fs::File::open(line)?;
+            let (header, bundle) = esvc_core::read_bundle::<Arg, _>(f, ENGINE_ID)?;
+            if header.hash_algo != self.g.hash_algo {
+                return Err(esvc_core::BundleError::HashAlgoMismatch {
+                    expected: self.g.hash_algo,
+                    got: header.hash_algo,
+                }
+                .into());
+            }
+            let heads = self.g.import_bundle(bundle)?;
+            self.merge_state(&heads)?;
+            true
+        } else if let Some(prefix) = line.strip_prefix("*patch ") {
+            let h = self.g.resolve_prefix(prefix.trim())?;
+            print!("{}", self.g.patch(&h)?);
+            true
+        } else if line == "p<" {
+            let mut line = String::new();
+            let stdin = std::io::stdin();
+            stdin.read_line(&mut line)?;
+            line.truncate(line.trim_end_matches(&['\r', '\n'][..]).len());
+
+            let patch = esvc_core::Patch::<Arg>::parse(&std::fs::read_to_string(line)?)?;
+            let h = self.g.apply_patch(patch)?;
+            self.merge_state(&[h].into_iter().collect())?;
+            true
+        } else if line == "f<" {
+            let mut line = String::new();
+            let stdin = std::io::stdin();
+            stdin.read_line(&mut line)?;
+            line.truncate(line.trim_end_matches(&['\r', '\n'][..]).len());
+
+            let conn = std::os::unix::net::UnixStream::connect(line)?;
+            let fetched = self.g.fetch(&conn, &conn, ENGINE_ID, "")?;
+            println!("fetched {} events", fetched.events.len());
+            self.merge_state(&fetched.heads)?;
+            true
         } else if line == "m<" {
             let mut line = String::new();
             let stdin = std::io::stdin();
             stdin.read_line(&mut line)?;
             line.truncate(line.trim_end_matches(&['\r', '\n'][..]).len());
 
-            let f = std::io::BufReader::new(std::fs::File::open(line)?);
-            let fz = zstd::stream::read::Decoder::new(f)?;
-            let tmpgraph = bincode::deserialize_from::<_, Graph<Arg>>(fz)?;
+            let tmpgraph = load_graph(line)?;
+            if tmpgraph.hash_algo != self.g.hash_algo {
+                // events would be rehashed during insertion, and the
+                // state of the other graph wouldn't be found anymore
+                anyhow::bail!(
+                    "other file uses hash algorithm {}, but this graph uses {}",
+                    tmpgraph.hash_algo,
+                    self.g.hash_algo,
+                );
+            }
 
             let other_estate = tmpgraph
                 .nstates
                 .get("")
                 .ok_or_else(|| anyhow::anyhow!("other file doesn't contain state set"))?;
+            if let Some(keyring) = &self.keyring {
+                // check the foreign events before they get inserted
+                let policy = esvc_core::MergePolicy {
+                    keyring,
+                    local: self.g.ancestors(&self.g.nstates[""])?,
+                };
+                if let Some((h, status)) = policy.check(&tmpgraph, other_estate)? {
+                    anyhow::bail!("refusing to merge event {} ({:?})", h, status);
+                }
+            }
             let full_odeps = tmpgraph.calculate_dependencies(
                 Default::default(),
                 other_estate
@@ -85,35 +422,52 @@ impl Context<'_> {
                 }
             }
             println!();
-            println!("minimize state...");
-            let xsts = self.g.nstates[""]
-                .iter()
-                .chain(other_estate.iter())
-                .map(|&h| (h, false))
-                .collect();
-            let xsts: std::collections::BTreeSet<_> = self
-                .g
-                .fold_state(xsts, false)?
-                .into_iter()
-                .map(|(h, _)| h)
-                .collect();
-            println!("try to merge...");
-            self.w
-                .try_merge(&mut self.g, xsts.clone())
-                .map_err(rewrap_wce)?;
-            println!("{}", Colour::Green.paint("OK"));
-            for h in &xsts {
-                println!("{} {}", Colour::Blue.paint(">>"), h);
-            }
-            if !xsts.is_empty() {
-                self.g.nstates.insert(String::new(), xsts);
-            }
+            self.g.merge_meta_from(&tmpgraph);
+            self.g.merge_signatures_from(&tmpgraph);
+            self.merge_state(other_estate)?;
             true
         } else {
             false
         })
     }
 
+    /// merge the state `other_estate` (whose events need to be present already)
+    /// into the current state
+    fn merge_state(
+        &mut self,
+        other_estate: &std::collections::BTreeSet<esvc_core::Hash>,
+    ) -> anyhow::Result<()> {
+        println!("minimize state...");
+        let xsts = self.g.nstates[""]
+            .iter()
+            .chain(other_estate.iter())
+            .map(|&h| (h, false))
+            .collect();
+        let xsts: std::collections::BTreeSet<_> =
+            self.g.fold_state_memo(xsts, false)?.into_keys().collect();
+        println!("try to merge...");
+        match &self.keyring {
+            Some(keyring) => {
+                let policy = esvc_core::MergePolicy {
+                    keyring,
+                    local: self.g.ancestors(&self.g.nstates[""])?,
+                };
+                self.w
+                    .try_merge_with_policy(&mut self.g, xsts.clone(), &policy)
+            }
+            None => self.w.try_merge(&mut self.g, xsts.clone()),
+        }
+        .map_err(rewrap_wce)?;
+        println!("{}", Colour::Green.paint("OK"));
+        for h in &xsts {
+            println!("{} {}", Colour::Blue.paint(">>"), self.g.abbrev(h));
+        }
+        if !xsts.is_empty() {
+            self.g.set_state("", xsts, ReflogReason::Merge);
+        }
+        Ok(())
+    }
+
     fn rick(&mut self, addr: addr::Address, ick: en::InpCommandKind) -> anyhow::Result<()> {
         use en::InpCommandKind as Ick;
         let state = &self.g.nstates[""];
@@ -217,7 +571,7 @@ impl Context<'_> {
         let state = self.g.nstates[""].clone();
         if let Some(h) = self
             .w
-            .shelve_event(
+            .shelve_event_with_meta(
                 &mut self.g,
                 state,
                 esvc_core::Event {
@@ -225,14 +579,21 @@ impl Context<'_> {
                     arg: pipelcmd,
                     deps: Default::default(),
                 },
+                esvc_core::EventMeta {
+                    author: std::env::var("USER").ok(),
+                    ..esvc_core::EventMeta::now()
+                },
             )
             .map_err(rewrap_wce)?
         {
-            println!("{} {}", Colour::Blue.paint(">>"), h);
+            println!("{} {}", Colour::Blue.paint(">>"), self.g.abbrev(&h));
+            if let Some(key) = &self.key {
+                self.g.sign_event(key, h)?;
+            }
             if self.g.nstates[""].len() > 100 {
                 let st = self
                     .g
-                    .fold_state(
+                    .fold_state_memo(
                         self.g.nstates[""]
                             .iter()
                             .chain(core::iter::once(&h))
@@ -243,9 +604,11 @@ impl Context<'_> {
                     .into_iter()
                     .map(|x| x.0)
                     .collect();
-                self.g.nstates.insert(String::new(), st);
+                self.g.set_state("", st, ReflogReason::Shelve);
             } else {
-                self.g.nstates.get_mut("").unwrap().insert(h);
+                let mut st = self.g.nstates[""].clone();
+                st.insert(h);
+                self.g.set_state("", st, ReflogReason::Shelve);
             }
         }
         Ok(())
@@ -260,27 +623,52 @@ fn main() -> anyhow::Result<()> {
     let e = en::ExEngine {
         rgxcache: Default::default(),
     };
+    let path: Option<camino::Utf8PathBuf> = arg.map(Into::into);
+    let (g, store) = match &path {
+        Some(path) if path == "--help" => {
+            println!("USAGE: exvc [GRAPH_FILE]");
+            println!(
+                "  files with the extension .{} are kept as append-only logs",
+                EVLOG_EXT
+            );
+            println!(
+                "  files with the extension .{} are kept as SQLite databases",
+                SQLITE_EXT
+            );
+            println!("  ${} (optional) signing key file, see *keygen", KEY_ENV);
+            println!(
+                "  ${} (optional) file with trusted keys, required for merges",
+                KEYRING_ENV
+            );
+            return Ok(());
+        }
+        Some(path) if path.extension() == Some(EVLOG_EXT) => {
+            let (g, log) = open_log(path)?;
+            (g, Some(Store::Log(log)))
+        }
+        Some(path) if path.extension() == Some(SQLITE_EXT) => {
+            let (g, db) = open_db(path)?;
+            (g, Some(Store::Sqlite(db)))
+        }
+        Some(path) if path.exists() => (load_graph(path)?, None),
+        _ => (Graph::default(), None),
+    };
     let mut ctx = Context {
-        path: None,
+        path,
         ps: SyntaxSet::load_defaults_newlines(),
         ts: ThemeSet::load_defaults(),
-        g: if let Some(arg) = &arg {
-            if std::path::Path::new(arg).exists() {
-                let f = std::io::BufReader::new(std::fs::File::open(arg)?);
-                let fz = zstd::stream::read::Decoder::new(f)?;
-                bincode::deserialize_from::<_, Graph<Arg>>(fz)?
-            } else if arg == "--help" {
-                println!("USAGE: exvc [GRAPH_FILE]");
-                return Ok(());
-            } else {
-                Graph::default()
-            }
-        } else {
-            Graph::default()
-        },
+        g,
         w: WorkCache::new(&e, vec![]),
+        store,
+        key: match std::env::var_os(KEY_ENV) {
+            Some(path) => Some(esvc_core::read_key_file(path.as_ref())?),
+            None => None,
+        },
+        keyring: match std::env::var_os(KEYRING_ENV) {
+            Some(path) => Some(Keyring::read(path.as_ref())?),
+            None => None,
+        },
     };
-    ctx.path = arg.map(Into::into);
 
     {
         let dfl_thpath: Option<&'static str> = core::option_env!("EXVC_DFL_THEME_PATH");